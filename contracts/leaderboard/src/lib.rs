@@ -3,15 +3,18 @@
 use core::cmp::Ordering;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
-    Env, IntoVal, InvokeError, String, Symbol, Val, Vec,
+    address_payload::AddressPayload, contract, contracterror, contractimpl, contracttype,
+    panic_with_error, Address, Bytes, BytesN, Env, IntoVal, InvokeError, String, Symbol, Val, Vec,
 };
 
 const TOP_N: u32 = 20;
+const JOURNAL_LEN: u32 = 72; // must match JOURNAL_LEN in zerotrust_zkvm/core/src/journal.rs
 const MIN_NAME_LEN: u32 = 1;
 const MAX_NAME_LEN: u32 = 24;
 const ASCII_PRINTABLE_MIN: u8 = 0x20;
 const ASCII_PRINTABLE_MAX: u8 = 0x7E;
+// Spent nonces live in temporary storage and drop out after ~30 days of 5s ledgers
+const CONSUMED_TTL_LEDGERS: u32 = 30 * 24 * 60 * 12;
 
 #[contract]
 pub struct Leaderboard;
@@ -24,6 +27,7 @@ pub struct ScoreEntry {
     pub accuracy_bps: u32,
     pub duration_ms: u32,
     pub bot_id: u32,
+    pub outcome: u32, // Outcome code from the journal, see core/src/journal.rs
     pub name: String,
     pub submitted_ledger: u32,
 }
@@ -37,6 +41,7 @@ pub struct LeaderboardRow {
     pub threats_cured: u32,
     pub accuracy_bps: u32,
     pub bot_id: u32,
+    pub outcome: u32,
 }
 
 #[contracttype]
@@ -44,8 +49,10 @@ enum DataKey {
     Admin,
     VerifierId,
     ImageId,
-    Best(Address), // global best per player, no challenge_id
-    Top,           // single global top list
+    Best(Address),          // global best per player, no challenge_id
+    Top,                    // single global top list
+    Challenge(Address),     // outstanding challenge nonce per player
+    Consumed(Address, u32), // (player, challenge_id) already used by a proof, temporary
 }
 
 #[contracterror]
@@ -56,6 +63,22 @@ pub enum Error {
     InvalidName = 3,
     InvalidImageId = 4,
     ProofVerificationFailed = 5,
+    InvalidJournal = 6,
+    PlayerMismatch = 7,
+    ChallengeMismatch = 8,
+    ChallengeConsumed = 9,
+}
+
+/// Fields of the guest journal that the leaderboard records.
+struct Journal {
+    challenge_id: u32,
+    player_pubkey: BytesN<32>,
+    bot_config_id: u32,
+    threats_cured: u32,
+    score: u32,
+    duration_ms: u32,
+    accuracy_bps: u32,
+    outcome: u32,
 }
 
 fn require_admin(env: &Env) -> Address {
@@ -87,7 +110,7 @@ fn read_verifier_id(env: &Env) -> Address {
 
 fn validate_name(env: &Env, name: &String) {
     let len = name.len();
-    if len < MIN_NAME_LEN || len > MAX_NAME_LEN {
+    if !(MIN_NAME_LEN..=MAX_NAME_LEN).contains(&len) {
         panic_with_error!(env, Error::InvalidName);
     }
     let mut buf = [0u8; MAX_NAME_LEN as usize];
@@ -99,6 +122,64 @@ fn validate_name(env: &Env, name: &String) {
     }
 }
 
+// Layout matches GameJournal::decode in zerotrust_zkvm/core/src/journal.rs
+fn decode_journal(env: &Env, journal: &Bytes) -> Journal {
+    if journal.len() != JOURNAL_LEN {
+        panic_with_error!(env, Error::InvalidJournal);
+    }
+    let mut buf = [0u8; JOURNAL_LEN as usize];
+    journal.copy_into_slice(&mut buf);
+
+    let read_u32 = |o: usize| u32::from_le_bytes([buf[o], buf[o + 1], buf[o + 2], buf[o + 3]]);
+    let mut pubkey = [0u8; 32];
+    pubkey.copy_from_slice(&buf[4..36]);
+
+    Journal {
+        challenge_id: read_u32(0),
+        player_pubkey: BytesN::from_array(env, &pubkey),
+        bot_config_id: read_u32(36),
+        threats_cured: read_u32(40),
+        score: read_u32(52),
+        duration_ms: read_u32(56),
+        accuracy_bps: read_u32(60),
+        outcome: read_u32(64),
+    }
+}
+
+fn require_player_matches(env: &Env, player: &Address, player_pubkey: &BytesN<32>) {
+    match player.to_payload() {
+        Some(AddressPayload::AccountIdPublicKeyEd25519(key)) if key == *player_pubkey => {}
+        _ => panic_with_error!(env, Error::PlayerMismatch),
+    }
+}
+
+// Each proof must carry the nonce issued to this player by new_challenge.
+// Spending it removes it, so a journal can't be submitted twice; the spent
+// nonce is remembered for CONSUMED_TTL_LEDGERS so new_challenge won't hand
+// it out again while old proofs for it are still around.
+fn consume_challenge(env: &Env, player: &Address, challenge_id: u32) {
+    let consumed_key = DataKey::Consumed(player.clone(), challenge_id);
+    if env.storage().temporary().has(&consumed_key) {
+        panic_with_error!(env, Error::ChallengeConsumed);
+    }
+
+    let storage = env.storage().persistent();
+    let challenge_key = DataKey::Challenge(player.clone());
+    let issued: Option<u32> = storage.get(&challenge_key);
+    if issued != Some(challenge_id) {
+        panic_with_error!(env, Error::ChallengeMismatch);
+    }
+
+    storage.remove(&challenge_key);
+    let temporary = env.storage().temporary();
+    temporary.set(&consumed_key, &true);
+    temporary.extend_ttl(&consumed_key, CONSUMED_TTL_LEDGERS, CONSUMED_TTL_LEDGERS);
+}
+
+fn draw_challenge_id(env: &Env) -> u32 {
+    u32::from_le_bytes(env.prng().gen::<[u8; 4]>())
+}
+
 fn verify_proof(
     env: &Env,
     verifier_id: &Address,
@@ -224,17 +305,38 @@ impl Leaderboard {
         env.storage().persistent().set(&DataKey::ImageId, &image_id);
     }
 
-    /// Submit a verified score. Proof is verified inside this call.
+    /// Issue a fresh challenge nonce for the player's next round. The proof
+    /// journal must carry it as challenge_id; issuing a new one replaces any
+    /// unused nonce.
+    pub fn new_challenge(env: Env, player: Address) -> u32 {
+        player.require_auth();
+
+        let mut challenge_id = draw_challenge_id(&env);
+        while env
+            .storage()
+            .temporary()
+            .has(&DataKey::Consumed(player.clone(), challenge_id))
+        {
+            challenge_id = draw_challenge_id(&env);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Challenge(player), &challenge_id);
+        challenge_id
+    }
+
+    /// Get the player's outstanding challenge nonce, if any
+    pub fn get_challenge(env: Env, player: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::Challenge(player))
+    }
+
+    /// Submit a verified score. The raw journal is decoded and hashed here,
+    /// so only the fields the guest actually committed are recorded.
     pub fn submit_score(
         env: Env,
         player: Address,
         name: String,
-        score: u32,
-        threats_cured: u32,
-        accuracy_bps: u32,
-        duration_ms: u32,
-        bot_id: u32,
-        journal_hash: BytesN<32>,
+        journal: Bytes,
         image_id: BytesN<32>,
         seal: Bytes,
     ) {
@@ -242,6 +344,10 @@ impl Leaderboard {
 
         validate_name(&env, &name);
 
+        // Decode journal and bind it to the submitting account
+        let decoded = decode_journal(&env, &journal);
+        require_player_matches(&env, &player, &decoded.player_pubkey);
+
         // Verify image_id matches deployed guest
         let stored_image_id = read_image_id(&env);
         if stored_image_id != image_id {
@@ -249,26 +355,31 @@ impl Leaderboard {
         }
 
         // Verify proof — panics if invalid
+        let journal_hash: BytesN<32> = env.crypto().sha256(&journal).into();
         let verifier_id = read_verifier_id(&env);
         verify_proof(&env, &verifier_id, &journal_hash, &image_id, &seal);
 
+        // Reject replays — the nonce is spent even if the score isn't a new best
+        consume_challenge(&env, &player, decoded.challenge_id);
+
         // Only store if better than existing best
         let best_key = DataKey::Best(player.clone());
         let best_existing: Option<ScoreEntry> = env.storage().persistent().get(&best_key);
         let should_update = match best_existing {
             None => true,
-            Some(ref entry) => score > entry.score,
+            Some(ref entry) => decoded.score > entry.score,
         };
         if !should_update {
             return;
         }
 
         let entry = ScoreEntry {
-            score,
-            threats_cured,
-            accuracy_bps,
-            duration_ms,
-            bot_id,
+            score: decoded.score,
+            threats_cured: decoded.threats_cured,
+            accuracy_bps: decoded.accuracy_bps,
+            duration_ms: decoded.duration_ms,
+            bot_id: decoded.bot_config_id,
+            outcome: decoded.outcome,
             name: name.clone(),
             submitted_ledger: env.ledger().sequence(),
         };
@@ -277,10 +388,11 @@ impl Leaderboard {
         let row = LeaderboardRow {
             player,
             name,
-            score,
-            threats_cured,
-            accuracy_bps,
-            bot_id,
+            score: decoded.score,
+            threats_cured: decoded.threats_cured,
+            accuracy_bps: decoded.accuracy_bps,
+            bot_id: decoded.bot_config_id,
+            outcome: decoded.outcome,
         };
         upsert_top(&env, row);
     }
@@ -298,3 +410,6 @@ impl Leaderboard {
             .unwrap_or_else(|| Vec::new(&env))
     }
 }
+
+#[cfg(test)]
+mod test;
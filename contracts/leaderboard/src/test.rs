@@ -0,0 +1,181 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    address_payload::AddressPayload,
+    contract, contractimpl,
+    testutils::{storage::Temporary as _, Address as _},
+    Address, Bytes, BytesN, Env, InvokeError, String,
+};
+
+use crate::{DataKey, Error, Leaderboard, LeaderboardClient, CONSUMED_TTL_LEDGERS, JOURNAL_LEN};
+
+const PLAYER_KEY: [u8; 32] = [7; 32];
+const OTHER_KEY: [u8; 32] = [9; 32];
+const IMAGE_ID: [u8; 32] = [1; 32];
+
+/// Stands in for the Groth16 verifier: every proof passes.
+#[contract]
+pub struct AcceptingVerifier;
+
+#[contractimpl]
+impl AcceptingVerifier {
+    pub fn verify(_env: Env, _seal: Bytes, _image_id: BytesN<32>, _journal: BytesN<32>) {}
+}
+
+fn setup(env: &Env) -> LeaderboardClient<'_> {
+    env.mock_all_auths();
+    let verifier = env.register(AcceptingVerifier, ());
+    let client = LeaderboardClient::new(env, &env.register(Leaderboard, ()));
+    client.init(
+        &Address::generate(env),
+        &verifier,
+        &BytesN::from_array(env, &IMAGE_ID),
+    );
+    client
+}
+
+fn account(env: &Env, key: [u8; 32]) -> Address {
+    Address::from_payload(
+        env,
+        AddressPayload::AccountIdPublicKeyEd25519(BytesN::from_array(env, &key)),
+    )
+}
+
+/// A journal in the guest's layout, with fixed values for the fields the
+/// tests don't vary.
+fn journal(env: &Env, challenge_id: u32, player_key: [u8; 32], score: u32) -> Bytes {
+    let mut buf = [0u8; JOURNAL_LEN as usize];
+    let mut put = |offset: usize, value: u32| {
+        buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    };
+    put(0, challenge_id);
+    put(36, 42); // bot_config_id
+    put(40, 4); // threats_cured
+    put(44, 1); // systems_destroyed
+    put(48, 0); // data_leaked_x100
+    put(52, score);
+    put(56, 61_000); // duration_ms
+    put(60, 8_000); // accuracy_bps
+    put(64, 3); // outcome: threats_resolved
+    put(68, 0b10); // systems_lost_mask
+    buf[4..36].copy_from_slice(&player_key);
+    Bytes::from_slice(env, &buf)
+}
+
+fn submit(
+    client: &LeaderboardClient,
+    player: &Address,
+    journal: &Bytes,
+) -> Result<(), Result<soroban_sdk::Error, InvokeError>> {
+    let env = &client.env;
+    client
+        .try_submit_score(
+            player,
+            &String::from_str(env, "alice"),
+            journal,
+            &BytesN::from_array(env, &IMAGE_ID),
+            &Bytes::new(env),
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn submit_score_records_decoded_journal() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+
+    let challenge_id = client.new_challenge(&player);
+    submit(&client, &player, &journal(&env, challenge_id, PLAYER_KEY, 9_100)).unwrap();
+
+    let best = client.get_best(&player).unwrap();
+    assert_eq!(best.score, 9_100);
+    assert_eq!(best.threats_cured, 4);
+    assert_eq!(best.accuracy_bps, 8_000);
+    assert_eq!(best.duration_ms, 61_000);
+    assert_eq!(best.bot_id, 42);
+    assert_eq!(best.outcome, 3);
+
+    let top = client.get_top();
+    assert_eq!(top.len(), 1);
+    let row = top.get_unchecked(0);
+    assert_eq!(row.player, player);
+    assert_eq!(row.score, 9_100);
+    assert_eq!(row.outcome, 3);
+}
+
+#[test]
+fn submit_score_rejects_malformed_journal() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+    client.new_challenge(&player);
+
+    let short = Bytes::from_slice(&env, &[0u8; JOURNAL_LEN as usize - 1]);
+    assert_eq!(submit(&client, &player, &short), Err(Ok(Error::InvalidJournal.into())));
+}
+
+#[test]
+fn submit_score_rejects_other_players_journal() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+
+    let challenge_id = client.new_challenge(&player);
+    let theirs = journal(&env, challenge_id, OTHER_KEY, 9_100);
+    assert_eq!(submit(&client, &player, &theirs), Err(Ok(Error::PlayerMismatch.into())));
+    assert_eq!(client.get_best(&player), None);
+}
+
+#[test]
+fn submit_score_rejects_replayed_journal() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+
+    let challenge_id = client.new_challenge(&player);
+    let proof = journal(&env, challenge_id, PLAYER_KEY, 9_100);
+    submit(&client, &player, &proof).unwrap();
+    assert_eq!(client.get_challenge(&player), None);
+
+    assert_eq!(submit(&client, &player, &proof), Err(Ok(Error::ChallengeConsumed.into())));
+
+    // The spent nonce is kept only for a bounded time
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .temporary()
+            .get_ttl(&DataKey::Consumed(player.clone(), challenge_id))
+    });
+    assert_eq!(ttl, CONSUMED_TTL_LEDGERS);
+}
+
+#[test]
+fn submit_score_rejects_unissued_challenge() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+
+    // No challenge issued yet
+    let proof = journal(&env, 1, PLAYER_KEY, 9_100);
+    assert_eq!(submit(&client, &player, &proof), Err(Ok(Error::ChallengeMismatch.into())));
+
+    let challenge_id = client.new_challenge(&player);
+    let wrong = journal(&env, challenge_id.wrapping_add(1), PLAYER_KEY, 9_100);
+    assert_eq!(submit(&client, &player, &wrong), Err(Ok(Error::ChallengeMismatch.into())));
+}
+
+#[test]
+fn new_challenge_replaces_unused_nonce() {
+    let env = Env::default();
+    let client = setup(&env);
+    let player = account(&env, PLAYER_KEY);
+
+    let first = client.new_challenge(&player);
+    let second = client.new_challenge(&player);
+    assert_ne!(first, second);
+    assert_eq!(client.get_challenge(&player), Some(second));
+
+    let stale = journal(&env, first, PLAYER_KEY, 9_100);
+    assert_eq!(submit(&client, &player, &stale), Err(Ok(Error::ChallengeMismatch.into())));
+    submit(&client, &player, &journal(&env, second, PLAYER_KEY, 9_100)).unwrap();
+}
@@ -33,6 +33,7 @@ import {
   SYSTEM_DESTRUCTION_THRESHOLD,
   COMMIT_TICKS,
   DATA_EXFIL_THRESHOLD,
+  MEMORY_MAX_ASSIGNMENTS,
   VICTORY_META,
 } from "@/lib/constants";
 import DeveloperCard from "@/components/developer-page/developer-card";
@@ -44,6 +45,7 @@ import { ProofResult, useProver } from "@/hooks/useProver";
 import { StrKey } from "@stellar/stellar-sdk";
 import { useLeaderboard } from "@/hooks/useLeaderboard";
 import { ArrowLeft, ShieldCheck } from "lucide-react";
+import { toast } from "sonner";
 import ThreatFlowBoard from "@/components/developer-page/threat-flow-board";
 
 function getVictoryCondition(bot: AvailableBot): VictoryConditionTag {
@@ -61,8 +63,13 @@ export default function DefensePage() {
   const [showGameOver, setShowGameOver] = useState(false);
   const [proofResult, setProofResult] = useState<ProofResult | null>(null);
 
-  const { submitScore, fetchPersonalBest, topScores, personalBest } =
-    useLeaderboard();
+  const {
+    getChallenge,
+    submitScore,
+    fetchPersonalBest,
+    topScores,
+    personalBest,
+  } = useLeaderboard();
   const intervalRef = useRef<NodeJS.Timeout | null>(null);
   const actionLogRef = useRef<
     {
@@ -341,11 +348,7 @@ export default function DefensePage() {
       const playerName = wallet?.publicKey
         ? wallet.publicKey.slice(0, 4) + "..." + wallet.publicKey.slice(-4)
         : "anon";
-      const ok = await submitScore(
-        proof,
-        playerName,
-        proof.journal.bot_config_id,
-      );
+      const ok = await submitScore(proof, playerName);
       if (ok) await fetchPersonalBest();
     };
     window.addEventListener("zk-proof-ready", handler);
@@ -358,7 +361,10 @@ export default function DefensePage() {
     if (!botConfig.id) return;
     const tokenId = parseInt(botConfig.id.replace("token_", ""), 10);
     if (isNaN(tokenId)) return;
-    await prove(SEED, tokenId, actionLogRef.current);
+    // The leaderboard only accepts a proof of its outstanding nonce
+    const challengeId = await getChallenge();
+    if (challengeId === null) return;
+    await prove(challengeId, tokenId, actionLogRef.current);
   };
 
   const handleDragEnd = (event: DragEndEvent) => {
@@ -367,6 +373,21 @@ export default function DefensePage() {
     if (!over || !gameState) return;
     const developerId = active.id as string;
     const threatId = over.id as string;
+    // The circuit rejects logs with more concurrent assignments than this, so
+    // the drop is refused here. A dev moving threats frees their own slot.
+    if ((selectedBot as BotConfigFE | null)?.resourceAttack === "memory") {
+      const open = gameState.threats.filter(
+        (t) =>
+          (t.assignedDeveloperId && t.assignedDeveloperId !== developerId) ||
+          (t.committingDevId && t.committingDevId !== developerId),
+      ).length;
+      if (open >= MEMORY_MAX_ASSIGNMENTS) {
+        toast.error("Memory leak", {
+          description: `At most ${MEMORY_MAX_ASSIGNMENTS} developers can be assigned at once`,
+        });
+        return;
+      }
+    }
     setGameState((prev) => {
       if (!prev) return null;
       const targetThreat = prev.threats.find((t) => t.id === threatId);
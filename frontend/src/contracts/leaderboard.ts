@@ -44,6 +44,7 @@ export interface ScoreEntry {
   bot_id: u32;
   duration_ms: u32;
   name: string;
+  outcome: u32;
   score: u32;
   submitted_ledger: u32;
   threats_cured: u32;
@@ -54,6 +55,7 @@ export interface LeaderboardRow {
   accuracy_bps: u32;
   bot_id: u32;
   name: string;
+  outcome: u32;
   player: string;
   score: u32;
   threats_cured: u32;
@@ -64,7 +66,11 @@ export const Errors = {
   2: {message:"NotInitialized"},
   3: {message:"InvalidName"},
   4: {message:"InvalidImageId"},
-  5: {message:"ProofVerificationFailed"}
+  5: {message:"ProofVerificationFailed"},
+  6: {message:"InvalidJournal"},
+  7: {message:"PlayerMismatch"},
+  8: {message:"ChallengeMismatch"},
+  9: {message:"ChallengeConsumed"}
 }
 
 export interface Client {
@@ -80,11 +86,26 @@ export interface Client {
    */
   set_image_id: ({image_id}: {image_id: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
 
+  /**
+   * Construct and simulate a new_challenge transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Issue a fresh challenge nonce for the player's next round. The proof
+   * journal must carry it as challenge_id; issuing a new one replaces any
+   * unused nonce.
+   */
+  new_challenge: ({player}: {player: string}, options?: MethodOptions) => Promise<AssembledTransaction<u32>>
+
+  /**
+   * Construct and simulate a get_challenge transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
+   * Get the player's outstanding challenge nonce, if any
+   */
+  get_challenge: ({player}: {player: string}, options?: MethodOptions) => Promise<AssembledTransaction<Option<u32>>>
+
   /**
    * Construct and simulate a submit_score transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Submit a verified score. Proof is verified inside this call.
+   * Submit a verified score. The raw journal is decoded and hashed here,
+   * so only the fields the guest actually committed are recorded.
    */
-  submit_score: ({player, name, score, threats_cured, accuracy_bps, duration_ms, bot_id, journal_hash, image_id, seal}: {player: string, name: string, score: u32, threats_cured: u32, accuracy_bps: u32, duration_ms: u32, bot_id: u32, journal_hash: Buffer, image_id: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
+  submit_score: ({player, name, journal, image_id, seal}: {player: string, name: string, journal: Buffer, image_id: Buffer, seal: Buffer}, options?: MethodOptions) => Promise<AssembledTransaction<null>>
 
   /**
    * Construct and simulate a get_best transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
@@ -116,12 +137,14 @@ export class Client extends ContractClient {
   }
   constructor(public readonly options: ContractClientOptions) {
     super(
-      new ContractSpec([ "AAAAAQAAAAAAAAAAAAAAClNjb3JlRW50cnkAAAAAAAcAAAAAAAAADGFjY3VyYWN5X2JwcwAAAAQAAAAAAAAABmJvdF9pZAAAAAAABAAAAAAAAAALZHVyYXRpb25fbXMAAAAABAAAAAAAAAAEbmFtZQAAABAAAAAAAAAABXNjb3JlAAAAAAAABAAAAAAAAAAQc3VibWl0dGVkX2xlZGdlcgAAAAQAAAAAAAAADXRocmVhdHNfY3VyZWQAAAAAAAAE",
-        "AAAAAQAAAAAAAAAAAAAADkxlYWRlcmJvYXJkUm93AAAAAAAGAAAAAAAAAAxhY2N1cmFjeV9icHMAAAAEAAAAAAAAAAZib3RfaWQAAAAAAAQAAAAAAAAABG5hbWUAAAAQAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAAAAAAABXNjb3JlAAAAAAAABAAAAAAAAAANdGhyZWF0c19jdXJlZAAAAAAAAAQ=",
-        "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAABQAAAAAAAAASQWxyZWFkeUluaXRpYWxpemVkAAAAAAABAAAAAAAAAA5Ob3RJbml0aWFsaXplZAAAAAAAAgAAAAAAAAALSW52YWxpZE5hbWUAAAAAAwAAAAAAAAAOSW52YWxpZEltYWdlSWQAAAAAAAQAAAAAAAAAF1Byb29mVmVyaWZpY2F0aW9uRmFpbGVkAAAAAAU=",
+      new ContractSpec([ "AAAAAQAAAAAAAAAAAAAAClNjb3JlRW50cnkAAAAAAAgAAAAAAAAADGFjY3VyYWN5X2JwcwAAAAQAAAAAAAAABmJvdF9pZAAAAAAABAAAAAAAAAALZHVyYXRpb25fbXMAAAAABAAAAAAAAAAEbmFtZQAAABAAAAAAAAAAB291dGNvbWUAAAAABAAAAAAAAAAFc2NvcmUAAAAAAAAEAAAAAAAAABBzdWJtaXR0ZWRfbGVkZ2VyAAAABAAAAAAAAAANdGhyZWF0c19jdXJlZAAAAAAAAAQ=",
+        "AAAAAQAAAAAAAAAAAAAADkxlYWRlcmJvYXJkUm93AAAAAAAHAAAAAAAAAAxhY2N1cmFjeV9icHMAAAAEAAAAAAAAAAZib3RfaWQAAAAAAAQAAAAAAAAABG5hbWUAAAAQAAAAAAAAAAdvdXRjb21lAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAAAAAAFc2NvcmUAAAAAAAAEAAAAAAAAAA10aHJlYXRzX2N1cmVkAAAAAAAABA==",
+        "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAACQAAAAAAAAASQWxyZWFkeUluaXRpYWxpemVkAAAAAAABAAAAAAAAAA5Ob3RJbml0aWFsaXplZAAAAAAAAgAAAAAAAAALSW52YWxpZE5hbWUAAAAAAwAAAAAAAAAOSW52YWxpZEltYWdlSWQAAAAAAAQAAAAAAAAAF1Byb29mVmVyaWZpY2F0aW9uRmFpbGVkAAAAAAUAAAAAAAAADkludmFsaWRKb3VybmFsAAAAAAAGAAAAAAAAAA5QbGF5ZXJNaXNtYXRjaAAAAAAABwAAAAAAAAARQ2hhbGxlbmdlTWlzbWF0Y2gAAAAAAAAIAAAAAAAAABFDaGFsbGVuZ2VDb25zdW1lZAAAAAAAAAk=",
         "AAAAAAAAAD5EZXBsb3kgb25jZTogc2V0cyBhZG1pbiwgdmVyaWZpZXIgY29udHJhY3QsIGFuZCBndWVzdCBpbWFnZV9pZAAAAAAABGluaXQAAAADAAAAAAAAAAVhZG1pbgAAAAAAABMAAAAAAAAAC3ZlcmlmaWVyX2lkAAAAABMAAAAAAAAACGltYWdlX2lkAAAD7gAAACAAAAAA",
         "AAAAAAAAADpBZG1pbiBjYW4gdXBkYXRlIGltYWdlX2lkIHdoZW4gZ3Vlc3QgcHJvZ3JhbSBpcyByZWRlcGxveWVkAAAAAAAMc2V0X2ltYWdlX2lkAAAAAQAAAAAAAAAIaW1hZ2VfaWQAAAPuAAAAIAAAAAA=",
-        "AAAAAAAAADxTdWJtaXQgYSB2ZXJpZmllZCBzY29yZS4gUHJvb2YgaXMgdmVyaWZpZWQgaW5zaWRlIHRoaXMgY2FsbC4AAAAMc3VibWl0X3Njb3JlAAAACgAAAAAAAAAGcGxheWVyAAAAAAATAAAAAAAAAARuYW1lAAAAEAAAAAAAAAAFc2NvcmUAAAAAAAAEAAAAAAAAAA10aHJlYXRzX2N1cmVkAAAAAAAABAAAAAAAAAAMYWNjdXJhY3lfYnBzAAAABAAAAAAAAAALZHVyYXRpb25fbXMAAAAABAAAAAAAAAAGYm90X2lkAAAAAAAEAAAAAAAAAAxqb3VybmFsX2hhc2gAAAPuAAAAIAAAAAAAAAAIaW1hZ2VfaWQAAAPuAAAAIAAAAAAAAAAEc2VhbAAAAA4AAAAA",
+        "AAAAAAAAAJhJc3N1ZSBhIGZyZXNoIGNoYWxsZW5nZSBub25jZSBmb3IgdGhlIHBsYXllcidzIG5leHQgcm91bmQuIFRoZSBwcm9vZgpqb3VybmFsIG11c3QgY2FycnkgaXQgYXMgY2hhbGxlbmdlX2lkOyBpc3N1aW5nIGEgbmV3IG9uZSByZXBsYWNlcyBhbnkKdW51c2VkIG5vbmNlLgAAAA1uZXdfY2hhbGxlbmdlAAAAAAAAAQAAAAAAAAAGcGxheWVyAAAAAAATAAAAAQAAAAQ=",
+        "AAAAAAAAADRHZXQgdGhlIHBsYXllcidzIG91dHN0YW5kaW5nIGNoYWxsZW5nZSBub25jZSwgaWYgYW55AAAADWdldF9jaGFsbGVuZ2UAAAAAAAABAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAABAAAD6AAAAAQ=",
+        "AAAAAAAAAIJTdWJtaXQgYSB2ZXJpZmllZCBzY29yZS4gVGhlIHJhdyBqb3VybmFsIGlzIGRlY29kZWQgYW5kIGhhc2hlZCBoZXJlLApzbyBvbmx5IHRoZSBmaWVsZHMgdGhlIGd1ZXN0IGFjdHVhbGx5IGNvbW1pdHRlZCBhcmUgcmVjb3JkZWQuAAAAAAAMc3VibWl0X3Njb3JlAAAABQAAAAAAAAAGcGxheWVyAAAAAAATAAAAAAAAAARuYW1lAAAAEAAAAAAAAAAHam91cm5hbAAAAAAOAAAAAAAAAAhpbWFnZV9pZAAAA+4AAAAgAAAAAAAAAARzZWFsAAAADgAAAAA=",
         "AAAAAAAAABxHZXQgYSBwbGF5ZXIncyBwZXJzb25hbCBiZXN0AAAACGdldF9iZXN0AAAAAQAAAAAAAAAGcGxheWVyAAAAAAATAAAAAQAAA+gAAAfQAAAAClNjb3JlRW50cnkAAA==",
         "AAAAAAAAABFHZXQgZ2xvYmFsIHRvcCAyMAAAAAAAAAdnZXRfdG9wAAAAAAAAAAABAAAD6gAAB9AAAAAOTGVhZGVyYm9hcmRSb3cAAA==" ]),
       options
@@ -130,6 +153,8 @@ export class Client extends ContractClient {
   public readonly fromJSON = {
     init: this.txFromJSON<null>,
         set_image_id: this.txFromJSON<null>,
+        new_challenge: this.txFromJSON<u32>,
+        get_challenge: this.txFromJSON<Option<u32>>,
         submit_score: this.txFromJSON<null>,
         get_best: this.txFromJSON<Option<ScoreEntry>>,
         get_top: this.txFromJSON<Array<LeaderboardRow>>
@@ -13,9 +13,6 @@ import type { ProofResult } from "@/hooks/useProver";
 const RPC_URL =
   process.env.NEXT_PUBLIC_RPC_URL ?? "https://soroban-testnet.stellar.org";
 
-const SELECTOR =
-  process.env.NEXT_PUBLIC_VERIFIER_CONTRACT_SELECTOR ?? "73c457ba";
-
 export type SubmitStatus = "idle" | "submitting" | "success" | "error";
 
 export function useLeaderboard() {
@@ -40,7 +37,6 @@ export function useLeaderboard() {
     async (
       proof: ProofResult,
       playerName: string,
-      botConfigId: number,
     ): Promise<boolean> => {
       const client = buildClient();
       if (!client || !wallet?.publicKey) {
@@ -52,18 +48,17 @@ export function useLeaderboard() {
       setSubmitError(null);
 
       try {
-        // seal = selector (4 bytes) + seal bytes
+        // proof.seal_hex already starts with the verifier selector
         const sealHex = proof.seal_hex.replace(/^0x/, "");
-        const selectorHex = SELECTOR.replace(/^0x/, "");
-        const fullSealHex = selectorHex + sealHex;
 
-        const sealBuf = Buffer.from(fullSealHex, "hex");
+        const sealBuf = Buffer.from(sealHex, "hex");
         const imageIdBuf = Buffer.from(
           proof.image_id_hex.replace(/^0x/, ""),
           "hex",
         );
-        const journalHashBuf = Buffer.from(
-          proof.journal_sha256_hex.replace(/^0x/, ""),
+        // The contract decodes and hashes the raw journal itself
+        const journalBuf = Buffer.from(
+          proof.journal_bytes_hex.replace(/^0x/, ""),
           "hex",
         );
 
@@ -71,24 +66,16 @@ export function useLeaderboard() {
           player: wallet.publicKey,
           name: playerName,
           score: proof.journal.score,
-          threats_cured: proof.journal.threats_cured,
-          accuracy_bps: proof.journal.accuracy_bps,
-          duration_ms: proof.journal.duration_ms,
-          bot_id: botConfigId,
-          journal_hash: proof.journal_sha256_hex,
+          outcome: proof.journal.outcome,
+          journal: proof.journal_bytes_hex,
           image_id: proof.image_id_hex,
-          seal: `0x${fullSealHex.slice(0, 16)}...`,
+          seal: `0x${sealHex.slice(0, 16)}...`,
         });
 
         const tx = await client.submit_score({
           player: wallet.publicKey,
           name: playerName,
-          score: proof.journal.score,
-          threats_cured: proof.journal.threats_cured,
-          accuracy_bps: proof.journal.accuracy_bps,
-          duration_ms: proof.journal.duration_ms,
-          bot_id: botConfigId,
-          journal_hash: journalHashBuf,
+          journal: journalBuf,
           image_id: imageIdBuf,
           seal: sealBuf,
         });
@@ -109,6 +96,29 @@ export function useLeaderboard() {
     [buildClient, wallet?.publicKey],
   );
 
+  // The nonce the next proof must carry: the player's unused challenge if
+  // there is one, otherwise a fresh one from new_challenge.
+  const getChallenge = useCallback(async (): Promise<number | null> => {
+    const client = buildClient();
+    if (!client || !wallet?.publicKey) {
+      setSubmitError("No wallet connected");
+      return null;
+    }
+    try {
+      const current = await client.get_challenge({ player: wallet.publicKey });
+      if (current.result !== undefined && current.result !== null) {
+        return current.result;
+      }
+      const tx = await client.new_challenge({ player: wallet.publicKey });
+      const sent = await tx.signAndSend({ force: true });
+      return sent.result;
+    } catch (e: any) {
+      console.error("[useLeaderboard] new_challenge error:", e);
+      setSubmitError(e?.message ?? "Could not get a challenge");
+      return null;
+    }
+  }, [buildClient, wallet?.publicKey]);
+
   const fetchTopScores = useCallback(async (): Promise<LeaderboardRow[]> => {
     const client = buildClient();
     if (!client) return [];
@@ -143,6 +153,7 @@ export function useLeaderboard() {
   );
 
   return {
+    getChallenge,
     submitScore,
     fetchTopScores,
     fetchPersonalBest,
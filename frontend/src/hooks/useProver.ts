@@ -16,7 +16,7 @@ export interface ProofResult {
   journal_sha256_hex: string;
   journal_bytes_hex: string;
   journal: {
-    challenge_id: string;
+    challenge_id: number;
     player_pubkey: string;
     bot_config_id: number;
     threats_cured: number;
@@ -25,6 +25,7 @@ export interface ProofResult {
     score: number;
     duration_ms: number;
     accuracy_bps: number;
+    outcome: string;
   };
 }
 
@@ -36,7 +37,7 @@ export type ProverStatus =
   | "error";
 
 export function useProver() {
-  const { wallet } = useWallet();
+  const { wallet, signMessage } = useWallet();
   const [status, setStatus] = useState<ProverStatus>("idle");
   const [error, setError] = useState<string | null>(null);
   const [result, setResult] = useState<ProofResult | null>(null);
@@ -53,7 +54,7 @@ export function useProver() {
 
   const prove = useCallback(
     async (
-      challengeId: string,
+      challengeId: number,
       botConfigId: number,
       actionLog: {
         dev_index: number;
@@ -77,18 +78,27 @@ export function useProver() {
         StrKey.decodeEd25519PublicKey(wallet.publicKey),
       ).toString("hex");
 
-      // Step 1: Submit, get job_id immediately
+      // Step 1: Sign and submit, get job_id immediately
       let job_id: string;
       try {
+        const body = JSON.stringify({
+          challenge_id: challengeId,
+          player_pubkey: pubkeyHex,
+          bot_config_id: botConfigId,
+          action_log: actionLog,
+        });
+        // The prover only accepts requests signed by the player's own key
+        const timestamp = Math.floor(Date.now() / 1000).toString();
+        const signature = await signMessage(`${timestamp}.${body}`);
+
         const resp = await fetch(`${PROVER_URL}/prove`, {
           method: "POST",
-          headers: { "Content-Type": "application/json" },
-          body: JSON.stringify({
-            challenge_id: challengeId,
-            player_pubkey: pubkeyHex,
-            bot_config_id: botConfigId,
-            action_log: actionLog,
-          }),
+          headers: {
+            "Content-Type": "application/json",
+            "X-Zero-Trust-Timestamp": timestamp,
+            "X-Zero-Trust-Signature": signature,
+          },
+          body,
         });
 
         const data = await resp.json();
@@ -168,7 +178,7 @@ export function useProver() {
         }
       }, POLL_INTERVAL_MS);
     },
-    [wallet?.publicKey, stopPolling],
+    [wallet?.publicKey, signMessage, stopPolling],
   );
 
   const reset = useCallback(() => {
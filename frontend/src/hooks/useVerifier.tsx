@@ -8,9 +8,6 @@ import type { ProofResult } from "@/hooks/useProver";
 const RPC_URL =
   process.env.NEXT_PUBLIC_RPC_URL ?? "https://soroban-testnet.stellar.org";
 
-const SELECTOR =
-  process.env.NEXT_PUBLIC_VERIFIER_CONTRACT_SELECTOR ?? "73c457ba";
-
 export function useVerifier() {
   const { wallet, signTransaction } = useWallet();
 
@@ -26,13 +23,10 @@ export function useVerifier() {
         signTransaction,
       });
 
-      // seal = selector (4 bytes) + seal bytes (rest)
-      // proof.seal_hex is "0x<hex>" — strip 0x, prepend selector
+      // proof.seal_hex is "0x<selector><seal>", ready to submit
       const sealHex = proof.seal_hex.replace(/^0x/, "");
-      const selectorHex = SELECTOR.replace(/^0x/, "");
-      const fullSealHex = selectorHex + sealHex;
 
-      const sealBuf = Buffer.from(fullSealHex, "hex");
+      const sealBuf = Buffer.from(sealHex, "hex");
       const imageIdBuf = Buffer.from(
         proof.image_id_hex.replace(/^0x/, ""),
         "hex",
@@ -44,7 +38,7 @@ export function useVerifier() {
       );
 
       console.log("[useVerifier] verify call:", {
-        seal: fullSealHex,
+        seal: sealHex,
         image_id: proof.image_id_hex,
         journal: proof.journal_sha256_hex,
       });
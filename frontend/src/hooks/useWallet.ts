@@ -23,6 +23,7 @@ export interface UseWalletReturn extends WalletState {
   signTransaction: (
     txXdr: string,
   ) => Promise<{ signedTxXdr: string; signerAddress?: string | undefined }>;
+  signMessage: (message: string) => Promise<string>;
   refreshBalance: () => Promise<void>;
   kitRef: React.RefObject<any>;
   networkPassphrase: string;
@@ -347,6 +348,48 @@ export function useWallet(): UseWalletReturn {
     },
     [],
   );
+
+  // ── Unified signMessage (SEP-53) ───────────────────────────────────────────
+  // Returns a base64 ed25519 signature over
+  // sha256("Stellar Signed Message:\n" + message), the form wallets produce.
+  const signMessage = useCallback(async (message: string): Promise<string> => {
+    const wallet = walletSourceRef.current;
+    if (!wallet) throw new Error("No wallet connected");
+
+    if (wallet === "ephemeral") {
+      // Read secret from state via functional update to avoid stale closure
+      return new Promise((resolve, reject) => {
+        setState((prev) => {
+          if (!prev.wallet?.secretKey) {
+            reject(new Error("Missing secret key"));
+            return prev;
+          }
+          import("@stellar/stellar-sdk").then((StellarSdk) => {
+            try {
+              const keypair = StellarSdk.Keypair.fromSecret(
+                prev.wallet!.secretKey!,
+              );
+              const digest = StellarSdk.hash(
+                Buffer.from(`Stellar Signed Message:\n${message}`, "utf8"),
+              );
+              resolve(keypair.sign(digest).toString("base64"));
+            } catch (err) {
+              reject(err);
+            }
+          });
+          return prev;
+        });
+      });
+    }
+
+    if (!kitRef.current) throw new Error("Wallet kit not initialized");
+    const result = await kitRef.current.signMessage(message, {
+      networkPassphrase: NETWORK_PASSPHRASE,
+      address: prevAddressRef.current,
+    });
+    return result.signedMessage;
+  }, []);
+
   // ── Refresh balance ────────────────────────────────────────────────────────
   const refreshBalance = useCallback(async () => {
     const publicKey = prevAddressRef.current;
@@ -372,6 +415,7 @@ export function useWallet(): UseWalletReturn {
     generateEphemeral,
     disconnect,
     signTransaction,
+    signMessage,
     refreshBalance,
     kitRef,
     networkPassphrase: NETWORK_PASSPHRASE,
@@ -13,8 +13,12 @@ export const ROUND_DURATION = 90;
 
 export const SYSTEM_DESTRUCTION_THRESHOLD = 3;
 export const DATA_EXFIL_THRESHOLD = 3; // count
-export const COMMIT_DURATION = 1.5; // seconds
+// seconds. Must match SWAP_WINDOW_MS in zerotrust_zkvm/core/src/config.rs
+export const COMMIT_DURATION = 1.5;
 export const COMMIT_TICKS = COMMIT_DURATION * 10; // at 100ms interval = 15 ticks
+// Memory attack: devs assigned or committing at once. Must match
+// MEMORY_MAX_ASSIGNMENTS in zerotrust_zkvm/core/src/config.rs
+export const MEMORY_MAX_ASSIGNMENTS = 3;
 
 export const BOT_TYPES: Record<
   BotTypeTag,
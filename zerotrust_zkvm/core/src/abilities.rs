@@ -0,0 +1,45 @@
+use alloc::string::String;
+
+// ─── Special abilities ───────────────────────────────────────────────────────
+
+pub const STEALTH_DELAY_MS: u32 = 3_000; // threat can't be worked on until spawn + delay
+pub const MUTATION_AFTER_MS: u32 = 15_000; // required skills re-rolled at spawn + delay
+pub const REPLICATION_DAMAGE: u32 = 50_000; // parent replicates once half damaged (× 1000)
+pub const REPLICATION_DELAY_MS: u32 = 2_000; // child spawns this long after that
+pub const ENCRYPTION_CURE_PCT: u32 = 75; // cure rate scaled to 75%
+pub const PERSISTENCE_DELAY_MS: u32 = 5_000; // cured threat re-opens this long after cure
+pub const PERSISTENCE_REOPEN_PROGRESS: u32 = 50_000; // re-opened threat keeps half its cure
+
+/// The bot's special abilities, parsed from `BotConfigZK::abilities`.
+/// Unknown tags are ignored and duplicates have no extra effect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Abilities {
+    /// Threats stay hidden for STEALTH_DELAY_MS after spawning.
+    pub stealth: bool,
+    /// Each threat re-rolls its required skills once, MUTATION_AFTER_MS after spawning.
+    pub mutation: bool,
+    /// A threat spawns one child once it reaches REPLICATION_DAMAGE;
+    /// children don't replicate.
+    pub replication: bool,
+    /// All cure rates are scaled by ENCRYPTION_CURE_PCT.
+    pub encryption: bool,
+    /// A cured threat re-opens once, PERSISTENCE_DELAY_MS after being cured.
+    pub persistence: bool,
+}
+
+impl Abilities {
+    pub fn from_tags(tags: &[String]) -> Self {
+        let mut abilities = Abilities::default();
+        for tag in tags {
+            match tag.as_str() {
+                "stealth" => abilities.stealth = true,
+                "mutation" => abilities.mutation = true,
+                "replication" => abilities.replication = true,
+                "encryption" => abilities.encryption = true,
+                "persistence" => abilities.persistence = true,
+                _ => {}
+            }
+        }
+        abilities
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Abilities;
+
+// ─── Round constants ──────────────────────────────────────────────────────────
+
+pub const ROUND_DURATION_MS: u32 = 90_000;
+pub const BASE_DAMAGE_RATE: u32 = 12; // 1.2% per second × 10, as damageRate in game-logic.ts
+pub const BASE_CURE_RATES: [u32; 4] = [30, 20, 10, 5]; // perfect/good/partial/poor × 10
+pub const TICK_MS: u32 = 100;
+// After this a dev is locked in until the threat resolves. Must match
+// COMMIT_DURATION in frontend/src/lib/constants.ts
+pub const SWAP_WINDOW_MS: u32 = 1_500;
+
+// Attacker win thresholds for each victory condition
+pub const DESTRUCTION_WIN_SYSTEMS: u32 = 3; // capped at the number of targeted systems
+pub const EXFIL_WIN_X100: u32 = 5_000; // 50.00% data leaked
+
+// Global debuffs for each resource attack
+pub const CPU_CURE_PCT: u32 = 80; // all cure rates scaled to 80%
+pub const MEMORY_MAX_ASSIGNMENTS: usize = 3; // concurrent assignments — must match constants.ts
+pub const BANDWIDTH_DELAY_MS: u32 = 1_000; // a dev starts curing this long after assignment
+pub const DISK_DAMAGE_PCT: u32 = 125; // all threat damage rates scaled to 125%
+
+// Skill pool — must match SKILL_POOL order in game-logic.ts exactly
+pub const SKILL_POOL: [&str; 9] = [
+    "python",
+    "rust",
+    "javascript",
+    "network",
+    "endpoint",
+    "crypto",
+    "database",
+    "web",
+    "forensics",
+];
+
+// Fixed developer pool — must match FIXED_DEVELOPER_POOL in game-logic.ts exactly
+pub const DEV_SKILLS: [[usize; 3]; 10] = [
+    [0, 3, 5], // Alice:   python, network, crypto
+    [1, 4, 6], // Bob:     rust, endpoint, database
+    [2, 7, 8], // Charlie: javascript, web, forensics
+    [5, 0, 8], // Diana:   crypto, python, forensics
+    [3, 4, 7], // Eve:     network, endpoint, web
+    [6, 1, 0], // Frank:   database, rust, python
+    [7, 2, 6], // Grace:   web, javascript, database
+    [8, 3, 1], // Hiro:    forensics, network, rust
+    [4, 5, 2], // Iris:    endpoint, crypto, javascript
+    [0, 6, 7], // Jin:     python, database, web
+];
+
+// ─── Inputs ───────────────────────────────────────────────────────────────────
+
+/// Bot config fetched from Stellar and passed into the zkVM as a public input.
+/// All floats converted to fixed-point integers (×100) to match on-chain storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotConfigZK {
+    pub bot_type: String,       // e.g. "malware"
+    pub primary_target: String, // e.g. "compute"
+    pub secondary_targets: Vec<String>,
+    pub damage_multiplier: u32, // on-chain u32, e.g. 150 = 1.5×
+    pub threat_count: u32,
+    pub spawn_pattern: String,     // "steady" | "burst" | "crescendo"
+    pub skill_diversity: String,   // "low" | "medium" | "high"
+    pub victory_condition: String, // "time-survival" | "system-destruction" | "data-exfiltration"
+    pub abilities: Vec<String>, // "stealth" | "mutation" | "replication" | "encryption" | "persistence"
+    pub resource_attack: String, // "cpu" | "memory" | "bandwidth" | "disk" | "none"
+}
+
+impl BotConfigZK {
+    pub fn resource_attack(&self) -> ResourceAttack {
+        ResourceAttack::from_tag(&self.resource_attack)
+    }
+
+    pub fn abilities(&self) -> Abilities {
+        Abilities::from_tags(&self.abilities)
+    }
+
+    /// Upper bound on threat indices an action log may reference: replication
+    /// can add one child per original threat.
+    pub fn max_threats(&self) -> usize {
+        let base = self.threat_count as usize;
+        if self.abilities().replication {
+            base * 2
+        } else {
+            base
+        }
+    }
+
+    pub fn victory(&self) -> VictoryCondition {
+        VictoryCondition::from_tag(&self.victory_condition)
+    }
+}
+
+/// How the attacker wins the round, parsed from `BotConfigZK::victory_condition`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VictoryCondition {
+    /// Defender only has to last the round; attacker wins only if every
+    /// targeted system is destroyed.
+    TimeSurvival,
+    /// Attacker wins once DESTRUCTION_WIN_SYSTEMS systems are destroyed.
+    SystemDestruction,
+    /// Attacker wins once data leaked reaches EXFIL_WIN_X100.
+    DataExfiltration,
+}
+
+impl VictoryCondition {
+    /// Accepts both the hyphenated tags produced by the host ("time-survival")
+    /// and the underscored form ("time_survival"). Unknown tags fall back to
+    /// TimeSurvival, like the other config lookups.
+    pub fn from_tag(tag: &str) -> Self {
+        match tag {
+            "system-destruction" | "system_destruction" => VictoryCondition::SystemDestruction,
+            "data-exfiltration" | "data_exfiltration" => VictoryCondition::DataExfiltration,
+            _ => VictoryCondition::TimeSurvival,
+        }
+    }
+}
+
+/// Global debuff applied for the whole round, parsed from
+/// `BotConfigZK::resource_attack`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceAttack {
+    /// Cure rates scaled by CPU_CURE_PCT.
+    Cpu,
+    /// At most MEMORY_MAX_ASSIGNMENTS devs assigned at once.
+    Memory,
+    /// Devs start curing BANDWIDTH_DELAY_MS after being assigned.
+    Bandwidth,
+    /// Threat damage rates scaled by DISK_DAMAGE_PCT.
+    Disk,
+    None,
+}
+
+impl ResourceAttack {
+    pub fn from_tag(tag: &str) -> Self {
+        match tag {
+            "cpu" => ResourceAttack::Cpu,
+            "memory" => ResourceAttack::Memory,
+            "bandwidth" => ResourceAttack::Bandwidth,
+            "disk" => ResourceAttack::Disk,
+            _ => ResourceAttack::None,
+        }
+    }
+
+    /// Time between a dev being assigned and starting to cure.
+    pub fn assignment_delay_ms(self) -> u32 {
+        match self {
+            ResourceAttack::Bandwidth => BANDWIDTH_DELAY_MS,
+            _ => 0,
+        }
+    }
+}
+
+/// Action log entry — private input from defender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionEntry {
+    pub dev_index: u32,
+    pub threat_index: u32,
+    pub assigned_at_ms: u32,
+    pub unassigned_at_ms: u32,
+}
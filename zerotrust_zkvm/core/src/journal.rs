@@ -0,0 +1,181 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+// ─── Journal layout ───────────────────────────────────────────────────────────
+// What the guest program commits via env::commit_slice
+//
+// Offset  Size  Field
+// 0       4     challenge_id         (u32 LE)
+// 4       32    player_pubkey        ([u8;32])
+// 36      4     bot_config_id        (u32 LE)
+// 40      4     threats_cured        (u32 LE)
+// 44      4     systems_destroyed    (u32 LE)
+// 48      4     data_leaked_x100     (u32 LE)  — e.g. 4550 = 45.50%
+// 52      4     score                (u32 LE)
+// 56      4     duration_ms          (u32 LE)
+// 60      4     accuracy_bps         (u32 LE)
+// 64      4     outcome              (u32 LE)  — see Outcome
+// 68      4     systems_lost_mask    (u32 LE)  — bit i set = SYSTEM_IDS[i] lost
+// ──────────────────────────────────────────────────────────────────────────────
+pub const JOURNAL_LEN: usize = 72;
+
+/// Which side won the round and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u32)]
+pub enum Outcome {
+    /// The round ran its full length without the attacker's victory condition firing.
+    DefenderSurvived = 0,
+    /// Attacker destroyed enough systems (or all of them, for time survival).
+    SystemsDestroyed = 1,
+    /// Attacker leaked enough data.
+    DataExfiltrated = 2,
+    /// Every threat was cured or failed before time ran out, without the
+    /// attacker's victory condition firing.
+    ThreatsResolved = 3,
+}
+
+impl Outcome {
+    pub fn attacker_won(self) -> bool {
+        matches!(self, Outcome::SystemsDestroyed | Outcome::DataExfiltrated)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::DefenderSurvived => "defender_survived",
+            Outcome::SystemsDestroyed => "systems_destroyed",
+            Outcome::DataExfiltrated => "data_exfiltrated",
+            Outcome::ThreatsResolved => "threats_resolved",
+        }
+    }
+
+    pub fn from_u32(code: u32) -> Option<Self> {
+        match code {
+            0 => Some(Outcome::DefenderSurvived),
+            1 => Some(Outcome::SystemsDestroyed),
+            2 => Some(Outcome::DataExfiltrated),
+            3 => Some(Outcome::ThreatsResolved),
+            _ => None,
+        }
+    }
+}
+
+/// Public outputs committed to by the guest — what gets posted on-chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameJournal {
+    pub challenge_id: u32,
+    pub player_pubkey: [u8; 32],
+    pub bot_config_id: u32,
+    pub threats_cured: u32,
+    pub systems_destroyed: u32,
+    pub data_leaked_x100: u32, // percentage × 100, e.g. 4550 = 45.50%
+    pub score: u32,
+    pub duration_ms: u32,
+    pub accuracy_bps: u32, // basis points, 10000 = 100%
+    pub outcome: Outcome,
+    pub systems_lost_mask: u32, // bit i set = SYSTEM_IDS[i] lost
+}
+
+/// Returned when journal bytes do not have the expected layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalError {
+    Length { got: usize },
+    Outcome(u32),
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::Length { got } => write!(
+                f,
+                "journal length mismatch: got {}, expected {}",
+                got, JOURNAL_LEN
+            ),
+            JournalError::Outcome(code) => write!(f, "unknown outcome code {}", code),
+        }
+    }
+}
+
+// ─── Journal encode / decode ──────────────────────────────────────────────────
+
+impl GameJournal {
+    pub fn encode(&self) -> [u8; JOURNAL_LEN] {
+        let mut journal = [0u8; JOURNAL_LEN];
+        let mut o = 0usize;
+
+        macro_rules! write_u32 {
+            ($v:expr) => {
+                journal[o..o + 4].copy_from_slice(&($v as u32).to_le_bytes());
+                o += 4;
+            };
+        }
+
+        write_u32!(self.challenge_id);
+        journal[o..o + 32].copy_from_slice(&self.player_pubkey);
+        o += 32;
+        write_u32!(self.bot_config_id);
+        write_u32!(self.threats_cured);
+        write_u32!(self.systems_destroyed);
+        write_u32!(self.data_leaked_x100);
+        write_u32!(self.score);
+        write_u32!(self.duration_ms);
+        write_u32!(self.accuracy_bps);
+        write_u32!(self.outcome);
+        write_u32!(self.systems_lost_mask);
+        debug_assert_eq!(o, JOURNAL_LEN);
+
+        journal
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, JournalError> {
+        if bytes.len() != JOURNAL_LEN {
+            return Err(JournalError::Length { got: bytes.len() });
+        }
+
+        let mut o = 0usize;
+
+        macro_rules! read_u32 {
+            () => {{
+                let v = u32::from_le_bytes(bytes[o..o + 4].try_into().unwrap());
+                o += 4;
+                v
+            }};
+        }
+
+        macro_rules! read_bytes32 {
+            () => {{
+                let mut arr = [0u8; 32];
+                arr.copy_from_slice(&bytes[o..o + 32]);
+                o += 32;
+                arr
+            }};
+        }
+
+        let challenge_id = read_u32!();
+        let player_pubkey = read_bytes32!();
+        let bot_config_id = read_u32!();
+        let threats_cured = read_u32!();
+        let systems_destroyed = read_u32!();
+        let data_leaked_x100 = read_u32!();
+        let score = read_u32!();
+        let duration_ms = read_u32!();
+        let accuracy_bps = read_u32!();
+        let outcome_code = read_u32!();
+        let outcome = Outcome::from_u32(outcome_code).ok_or(JournalError::Outcome(outcome_code))?;
+        let systems_lost_mask = read_u32!();
+        debug_assert_eq!(o, JOURNAL_LEN);
+
+        Ok(GameJournal {
+            challenge_id,
+            player_pubkey,
+            bot_config_id,
+            threats_cured,
+            systems_destroyed,
+            data_leaked_x100,
+            score,
+            duration_ms,
+            accuracy_bps,
+            outcome,
+            systems_lost_mask,
+        })
+    }
+}
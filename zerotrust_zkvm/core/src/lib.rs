@@ -0,0 +1,23 @@
+//! Game rules shared by the RISC Zero guest, the host prover and the
+//! frontend-facing server. Everything that affects the proven journal lives
+//! here, so the guest and the journal decoder cannot drift apart.
+
+#![no_std]
+
+extern crate alloc;
+
+mod abilities;
+mod config;
+mod journal;
+mod rng;
+mod sim;
+mod systems;
+mod threats;
+
+pub use abilities::*;
+pub use config::*;
+pub use journal::*;
+pub use rng::Rng;
+pub use sim::*;
+pub use systems::*;
+pub use threats::*;
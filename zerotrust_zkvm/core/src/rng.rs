@@ -0,0 +1,37 @@
+// ─── Seeded RNG — matches seedrandom behaviour ────────────────────────────────
+// Simple xorshift64 seeded from the sha256 seed string bytes
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: &str) -> Self {
+        // Mix seed bytes into u64 state
+        let mut state: u64 = 0x853c49e6748fea9b;
+        for b in seed.bytes() {
+            state ^= b as u64;
+            state = state
+                .wrapping_mul(0x5851f42d4c957f2d)
+                .wrapping_add(0x14057b7ef767814f);
+        }
+        Rng { state }
+    }
+
+    // Returns value in [0, 1) as fixed-point × 1_000_000
+    pub fn next_fp(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state % 1_000_000) as u32
+    }
+
+    pub fn next_usize(&mut self, n: usize) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        // Truncate to 32 bits so native builds match the rv32 guest, where
+        // usize is 32 bits wide.
+        (self.state as u32 as usize) % n
+    }
+}
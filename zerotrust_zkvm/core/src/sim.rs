@@ -0,0 +1,770 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::abilities::{
+    Abilities, PERSISTENCE_DELAY_MS, PERSISTENCE_REOPEN_PROGRESS, REPLICATION_DAMAGE,
+    REPLICATION_DELAY_MS,
+};
+use crate::config::{
+    ActionEntry, BotConfigZK, ResourceAttack, VictoryCondition, DESTRUCTION_WIN_SYSTEMS,
+    DEV_SKILLS, EXFIL_WIN_X100, MEMORY_MAX_ASSIGNMENTS, ROUND_DURATION_MS, SWAP_WINDOW_MS, TICK_MS,
+};
+use crate::journal::{GameJournal, Outcome};
+use crate::systems::{target_systems, System, THREAT_FAILURE_DAMAGE};
+use crate::threats::{effective_cure_speed, generate_threats, Threat};
+
+/// Reasons an action log is rejected before simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidActionLog {
+    DevIndexOutOfBounds,
+    ThreatIndexOutOfBounds,
+    InvalidAssignmentWindow,
+    AssignmentAfterRoundEnd,
+    DevDoubleAssigned,
+    TooManyConcurrentAssignments,
+    AssignedToResolvedThreat,
+    UnassignedWhileLocked,
+}
+
+impl fmt::Display for InvalidActionLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            InvalidActionLog::DevIndexOutOfBounds => "dev_index out of bounds",
+            InvalidActionLog::ThreatIndexOutOfBounds => "threat_index out of bounds",
+            InvalidActionLog::InvalidAssignmentWindow => "invalid assignment window",
+            InvalidActionLog::AssignmentAfterRoundEnd => "assignment after round end",
+            InvalidActionLog::DevDoubleAssigned => "dev double-assigned",
+            InvalidActionLog::TooManyConcurrentAssignments => {
+                "too many concurrent assignments under memory attack"
+            }
+            InvalidActionLog::AssignedToResolvedThreat => "dev assigned to a resolved threat",
+            InvalidActionLog::UnassignedWhileLocked => {
+                "dev unassigned after swap window while threat still active"
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+// ─── Seed — same as FE: sha256(pubkey_hex + bot_type) ────────────────────────
+// The threats depend only on the player and the bot; the challenge_id is the
+// leaderboard's nonce and is just committed to the journal.
+
+pub fn round_seed(player_pubkey: &[u8; 32], bot_type: &str) -> String {
+    let mut seed = hex_encode(player_pubkey);
+    seed.push_str(bot_type);
+    seed
+}
+
+// ─── Score — mirrors calculateScore ──────────────────────────────────────────
+
+pub fn calculate_score(
+    threats_cured: u32,
+    threats_total: u32,
+    systems_destroyed: u32,
+    duration_ms: u32,
+) -> u32 {
+    let accuracy_bps = threats_cured * 10_000 / threats_total.max(1);
+
+    let baseline_ms = threats_total * 8_000;
+    // timeFactor = min(2.0, baseline / duration) × 100
+    let time_factor = (baseline_ms * 100)
+        .checked_div(duration_ms)
+        .map_or(200, |tf| tf.min(200));
+
+    let damage_penalty = systems_destroyed * 50;
+    accuracy_bps
+        .saturating_add(time_factor)
+        .saturating_sub(damage_penalty)
+}
+
+// ─── Victory conditions ──────────────────────────────────────────────────────
+
+// data_leaked_x100: scale to percentage × 100
+// max possible leaked ticks = total_threats, so:
+fn data_leaked_x100(data_leaked_ticks: u32, total_threats: u32) -> u32 {
+    (data_leaked_ticks * 10_000 / total_threats.max(1)).min(10_000)
+}
+
+/// Returns the attacker's win reason once its victory condition is met.
+/// `systems_total` is the number of systems the bot targets.
+pub fn attacker_victory(
+    condition: VictoryCondition,
+    systems_destroyed: u32,
+    data_leaked_x100: u32,
+    systems_total: u32,
+) -> Option<Outcome> {
+    let won = match condition {
+        VictoryCondition::TimeSurvival => systems_destroyed >= systems_total.max(1),
+        VictoryCondition::SystemDestruction => {
+            systems_destroyed >= DESTRUCTION_WIN_SYSTEMS.min(systems_total.max(1))
+        }
+        VictoryCondition::DataExfiltration => data_leaked_x100 >= EXFIL_WIN_X100,
+    };
+    if !won {
+        return None;
+    }
+    Some(match condition {
+        VictoryCondition::DataExfiltration => Outcome::DataExfiltrated,
+        _ => Outcome::SystemsDestroyed,
+    })
+}
+
+// ─── Action log validation ───────────────────────────────────────────────────
+
+pub fn validate_action_log(
+    action_log: &[ActionEntry],
+    config: &BotConfigZK,
+) -> Result<(), InvalidActionLog> {
+    // Threat indices may reach Replication children that spawn later
+    let max_threats = config.max_threats();
+
+    // 1. All indices in bounds
+    for entry in action_log {
+        if entry.dev_index as usize >= DEV_SKILLS.len() {
+            return Err(InvalidActionLog::DevIndexOutOfBounds);
+        }
+        if entry.threat_index as usize >= max_threats {
+            return Err(InvalidActionLog::ThreatIndexOutOfBounds);
+        }
+        if entry.unassigned_at_ms <= entry.assigned_at_ms {
+            return Err(InvalidActionLog::InvalidAssignmentWindow);
+        }
+        if entry.assigned_at_ms > ROUND_DURATION_MS {
+            return Err(InvalidActionLog::AssignmentAfterRoundEnd);
+        }
+    }
+
+    // Later checks walk the log in assignment order
+    let mut by_start: Vec<&ActionEntry> = action_log.iter().collect();
+    by_start.sort_unstable_by_key(|a| a.assigned_at_ms);
+
+    // 2. No dev assigned to two threats simultaneously — in assignment order,
+    //    a dev's window overlaps an earlier one iff it starts before the
+    //    latest unassignment seen for that dev.
+    let mut dev_busy_until = [0u32; DEV_SKILLS.len()];
+    for entry in &by_start {
+        let busy_until = &mut dev_busy_until[entry.dev_index as usize];
+        if entry.assigned_at_ms < *busy_until {
+            return Err(InvalidActionLog::DevDoubleAssigned);
+        }
+        *busy_until = entry.unassigned_at_ms;
+    }
+
+    // 3. Memory attack caps how many devs can be assigned at once. The number
+    //    of open windows only rises at an assignment, so checking there is enough.
+    if config.resource_attack() == ResourceAttack::Memory {
+        let mut open: Vec<u32> = Vec::new(); // unassigned_at_ms of open windows
+        for entry in &by_start {
+            let t = entry.assigned_at_ms;
+            open.retain(|&until| until > t);
+            open.push(entry.unassigned_at_ms);
+            if open.len() > MEMORY_MAX_ASSIGNMENTS {
+                return Err(InvalidActionLog::TooManyConcurrentAssignments);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the swap window and lock-in rule against the simulated threat
+/// timeline. A dev may be unassigned within SWAP_WINDOW_MS of assignment;
+/// after that only once the threat has resolved or the round has ended. A dev
+/// can never be assigned to a threat that has already resolved.
+pub fn validate_lock_in(
+    action_log: &[ActionEntry],
+    threats: &[Threat],
+    round_end_ms: u32,
+) -> Result<(), InvalidActionLog> {
+    for entry in action_log {
+        let Some(threat) = threats.get(entry.threat_index as usize) else {
+            // Replication child that never spawned — nothing to resolve
+            continue;
+        };
+
+        // Resolved on an earlier tick — the threat was gone when assigned
+        let gone = threat
+            .resolved_spans
+            .iter()
+            .any(|&(from, until)| from < entry.assigned_at_ms && entry.assigned_at_ms < until);
+        if gone {
+            return Err(InvalidActionLog::AssignedToResolvedThreat);
+        }
+
+        let in_swap_window = entry.unassigned_at_ms <= entry.assigned_at_ms + SWAP_WINDOW_MS;
+        if !in_swap_window
+            && entry.unassigned_at_ms < round_end_ms
+            && !threat.resolved_at(entry.unassigned_at_ms)
+        {
+            return Err(InvalidActionLog::UnassignedWhileLocked);
+        }
+    }
+
+    Ok(())
+}
+
+// ─── Round simulation ────────────────────────────────────────────────────────
+// Event-driven: a tick is stepped one at a time only when something can change
+// on it — a spawn, an assignment starting or ending, a threat being cured or
+// failing, a system going down. The ticks in between are applied in one go,
+// with exactly the result stepping through them would give.
+
+const ROUND_TICKS: u32 = ROUND_DURATION_MS / TICK_MS;
+const THREAT_DONE: u32 = 100_000; // cure_progress / current_damage × 1000 fixed-point
+
+/// First tick at or after `ms`.
+fn tick_at(ms: u32) -> u32 {
+    ms.div_ceil(TICK_MS)
+}
+
+/// Ticks of `per_tick` needed to take `from` up to `to`; None if it never will.
+fn ticks_to_reach(from: u32, to: u32, per_tick: u32) -> Option<u32> {
+    if per_tick == 0 {
+        return None;
+    }
+    Some(to.saturating_sub(from).div_ceil(per_tick))
+}
+
+/// An action log entry as a span of ticks on its threat, [from, until), with
+/// any Bandwidth delay already applied.
+struct Window {
+    from: u32,
+    until: u32,
+    dev: usize,
+}
+
+/// Buckets the log by threat index, keeping log order within each threat.
+fn assignment_windows(
+    action_log: &[ActionEntry],
+    threat_slots: usize,
+    assignment_delay_ms: u32,
+) -> Vec<Vec<Window>> {
+    let mut windows: Vec<Vec<Window>> = (0..threat_slots).map(|_| Vec::new()).collect();
+    for entry in action_log {
+        windows[entry.threat_index as usize].push(Window {
+            from: tick_at(entry.assigned_at_ms + assignment_delay_ms),
+            until: tick_at(entry.unassigned_at_ms),
+            dev: entry.dev_index as usize,
+        });
+    }
+    windows
+}
+
+/// Every tick on which some assignment starts or ends, sorted.
+fn window_boundaries(windows: &[Vec<Window>]) -> Vec<u32> {
+    let mut ticks: Vec<u32> = windows
+        .iter()
+        .flatten()
+        .flat_map(|w| [w.from, w.until])
+        .filter(|&t| t < ROUND_TICKS)
+        .collect();
+    ticks.sort_unstable();
+    ticks.dedup();
+    ticks
+}
+
+/// Dev curing the threat at `tick`: the first log entry covering it, once
+/// Stealth no longer hides the threat.
+fn active_dev(threat: &Threat, windows: &[Window], tick: u32) -> Option<usize> {
+    if tick * TICK_MS < threat.visible_at_ms {
+        return None;
+    }
+    windows
+        .iter()
+        .find(|w| w.from <= tick && tick < w.until)
+        .map(|w| w.dev)
+}
+
+struct Round {
+    threats: Vec<Threat>,
+    systems: Vec<System>,
+    windows: Vec<Vec<Window>>,
+    abilities: Abilities,
+    resource_attack: ResourceAttack,
+    threats_cured: u32,
+    systems_destroyed: u32,
+    systems_lost_mask: u32,
+    data_leaked_ticks: u32, // count of unattended failed ticks for exfil
+}
+
+impl Round {
+    fn windows(&self, ti: usize) -> &[Window] {
+        self.windows.get(ti).map_or(&[], Vec::as_slice)
+    }
+
+    /// Plays a single tick.
+    fn step(&mut self, tick: u32) {
+        let tick_ms = tick * TICK_MS;
+        let abilities = self.abilities;
+        let mut children: Vec<Threat> = Vec::new();
+
+        for ti in 0..self.threats.len() {
+            let windows = self.windows.get(ti).map_or(&[][..], Vec::as_slice);
+            let threat = &mut self.threats[ti];
+
+            // Persistence — a cured threat re-opens once, half cured
+            if threat.is_cured {
+                if abilities.persistence
+                    && !threat.reopened
+                    && tick_ms >= threat.cured_at_ms + PERSISTENCE_DELAY_MS
+                {
+                    threat.is_cured = false;
+                    threat.reopened = true;
+                    if let Some(span) = threat.resolved_spans.last_mut() {
+                        span.1 = tick_ms;
+                    }
+                    threat.cure_progress = PERSISTENCE_REOPEN_PROGRESS;
+                    self.threats_cured -= 1;
+                } else {
+                    continue;
+                }
+            }
+            if threat.is_failed {
+                continue;
+            }
+            if threat.spawn_time_ms > tick_ms {
+                continue;
+            }
+
+            // Mutation — required skills re-rolled once, mid-life
+            if matches!(threat.mutation, Some((at_ms, _)) if at_ms <= tick_ms) {
+                if let Some((_, skills)) = threat.mutation.take() {
+                    threat.required_skill_indices = skills;
+                }
+            }
+
+            // Stealth hides the threat from defenders until it becomes
+            // visible, and a Bandwidth attack holds each dev back for a moment
+            // after assignment
+            let active_dev = active_dev(threat, windows, tick);
+            threat.assigned_dev = active_dev;
+
+            // Damage — only accumulates when no dev assigned, on both the
+            // threat and the system it targets
+            let system = &mut self.systems[threat.system];
+            let mut system_lost = false;
+            if active_dev.is_none() {
+                // damage_rate is already per-tick × 1000
+                threat.current_damage += threat.damage_rate;
+                system_lost |= system.take_damage(threat.damage_rate);
+
+                // Replication — originals spawn one child once half damaged
+                if threat.can_replicate(abilities) && threat.current_damage >= REPLICATION_DAMAGE {
+                    threat.replicated = true;
+                    children.push(threat.replicate(tick_ms + REPLICATION_DELAY_MS, abilities));
+                }
+            }
+
+            // Cure progress
+            if let Some(dev) = active_dev {
+                threat.cure_progress += effective_cure_speed(
+                    &threat.required_skill_indices,
+                    dev,
+                    abilities,
+                    self.resource_attack,
+                );
+            }
+
+            // Check outcomes (× 1000 fixed-point, 100_000 = 100%)
+            if threat.cure_progress >= THREAT_DONE {
+                threat.is_cured = true;
+                threat.cured_at_ms = tick_ms;
+                threat.resolved_spans.push((tick_ms, u32::MAX));
+                self.threats_cured += 1;
+            } else if threat.current_damage >= THREAT_DONE {
+                threat.is_failed = true;
+                threat.resolved_spans.push((tick_ms, u32::MAX));
+                system_lost |= system.take_damage(THREAT_FAILURE_DAMAGE);
+                // Count unattended failures for data exfil
+                if active_dev.is_none() {
+                    self.data_leaked_ticks += 1;
+                }
+            }
+
+            if system_lost {
+                self.systems_destroyed += 1;
+                self.systems_lost_mask |= 1 << system.id;
+            }
+        }
+
+        // Children take the next threat indices, in the order their parents replicated
+        self.threats.extend(children);
+    }
+
+    /// How the round ended, if it did on the last tick played.
+    fn finished(&self, victory: VictoryCondition) -> Option<Outcome> {
+        let total_threats = self.threats.len() as u32;
+        if let Some(reason) = attacker_victory(
+            victory,
+            self.systems_destroyed,
+            data_leaked_x100(self.data_leaked_ticks, total_threats),
+            self.systems.len() as u32,
+        ) {
+            return Some(reason);
+        }
+
+        // Round is over once nothing is left to spawn, cure or re-open
+        if self.threats.iter().all(|t| t.is_resolved(self.abilities)) {
+            return Some(Outcome::ThreatsResolved);
+        }
+
+        None
+    }
+
+    /// Finds the next tick after `tick` on which anything can change, applies
+    /// every tick in between at once and returns it.
+    fn fast_forward(&mut self, tick: u32, boundaries: &[u32]) -> u32 {
+        let mut next = ROUND_TICKS;
+        let upcoming = boundaries.partition_point(|&b| b <= tick);
+        if let Some(&b) = boundaries.get(upcoming) {
+            next = next.min(b);
+        }
+
+        // Per-tick progress of every live threat until the next event
+        let mut cures: Vec<(usize, usize, u32)> = Vec::new(); // (threat, dev, rate)
+        let mut drain = vec![0u32; self.systems.len()];
+        for (ti, threat) in self.threats.iter().enumerate() {
+            if threat.is_failed {
+                continue;
+            }
+            if threat.is_cured {
+                if self.abilities.persistence && !threat.reopened {
+                    next = next.min(tick_at(threat.cured_at_ms + PERSISTENCE_DELAY_MS));
+                }
+                continue;
+            }
+
+            let mutation_ms = threat.mutation.as_ref().map(|&(at_ms, _)| at_ms);
+            for at_ms in [
+                Some(threat.spawn_time_ms),
+                Some(threat.visible_at_ms),
+                mutation_ms,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let at = tick_at(at_ms);
+                if at > tick {
+                    next = next.min(at);
+                }
+            }
+            if tick_at(threat.spawn_time_ms) > tick {
+                continue;
+            }
+
+            match active_dev(threat, self.windows(ti), tick + 1) {
+                Some(dev) => {
+                    let rate = effective_cure_speed(
+                        &threat.required_skill_indices,
+                        dev,
+                        self.abilities,
+                        self.resource_attack,
+                    );
+                    if let Some(n) = ticks_to_reach(threat.cure_progress, THREAT_DONE, rate) {
+                        next = next.min(tick + n);
+                    }
+                    cures.push((ti, dev, rate));
+                }
+                None => {
+                    if let Some(n) =
+                        ticks_to_reach(threat.current_damage, THREAT_DONE, threat.damage_rate)
+                    {
+                        next = next.min(tick + n);
+                    }
+                    if threat.can_replicate(self.abilities) {
+                        if let Some(n) = ticks_to_reach(
+                            threat.current_damage,
+                            REPLICATION_DAMAGE,
+                            threat.damage_rate,
+                        ) {
+                            next = next.min(tick + n);
+                        }
+                    }
+                    drain[threat.system] += self.systems[threat.system].scaled(threat.damage_rate);
+                }
+            }
+        }
+        for (system, &per_tick) in self.systems.iter().zip(&drain) {
+            if !system.is_lost {
+                if let Some(n) = ticks_to_reach(0, system.health, per_tick) {
+                    next = next.min(tick + n);
+                }
+            }
+        }
+
+        // Nothing crosses a threshold before `next`, so the skipped ticks are
+        // plain accumulation
+        let skipped = next - tick - 1;
+        if skipped > 0 {
+            let mut cured = cures.iter().peekable();
+            for (ti, threat) in self.threats.iter_mut().enumerate() {
+                if threat.is_cured || threat.is_failed || tick_at(threat.spawn_time_ms) > tick {
+                    continue;
+                }
+                match cured.next_if(|&&(ci, _, _)| ci == ti) {
+                    Some(&(_, dev, rate)) => {
+                        threat.assigned_dev = Some(dev);
+                        threat.cure_progress += rate * skipped;
+                    }
+                    None => {
+                        threat.assigned_dev = None;
+                        threat.current_damage += threat.damage_rate * skipped;
+                    }
+                }
+            }
+            for (system, &per_tick) in self.systems.iter_mut().zip(&drain) {
+                if !system.is_lost {
+                    system.health -= per_tick * skipped;
+                }
+            }
+        }
+
+        next
+    }
+}
+
+/// Regenerates the bot's threats, validates the action log against them and
+/// simulates the round. The result is exactly what the guest commits to its
+/// journal.
+pub fn play_round(
+    challenge_id: u32,
+    player_pubkey: [u8; 32],
+    bot_config_id: u32,
+    config: &BotConfigZK,
+    action_log: &[ActionEntry],
+) -> Result<GameJournal, InvalidActionLog> {
+    play_round_with(
+        challenge_id,
+        player_pubkey,
+        bot_config_id,
+        config,
+        action_log,
+        Round::fast_forward,
+    )
+}
+
+/// play_round with the step to the next tick left to `advance`, which must
+/// apply every tick it skips. Tests pass one that steps every tick.
+fn play_round_with(
+    challenge_id: u32,
+    player_pubkey: [u8; 32],
+    bot_config_id: u32,
+    config: &BotConfigZK,
+    action_log: &[ActionEntry],
+    advance: fn(&mut Round, u32, &[u32]) -> u32,
+) -> Result<GameJournal, InvalidActionLog> {
+    validate_action_log(action_log, config)?;
+
+    // ── Generate threats deterministically ───────────────────────────────────
+    let seed = round_seed(&player_pubkey, &config.bot_type);
+    let resource_attack = config.resource_attack();
+    let windows = assignment_windows(
+        action_log,
+        config.max_threats(),
+        resource_attack.assignment_delay_ms(),
+    );
+    let boundaries = window_boundaries(&windows);
+    let mut round = Round {
+        threats: generate_threats(config, &seed),
+        systems: target_systems(config),
+        windows,
+        abilities: config.abilities(),
+        resource_attack,
+        threats_cured: 0,
+        systems_destroyed: 0,
+        systems_lost_mask: 0,
+        data_leaked_ticks: 0,
+    };
+
+    // ── Simulate event by event ───────────────────────────────────────────────
+    // The round ends early as soon as the attacker's victory condition fires.
+    let victory = config.victory();
+    let mut outcome = Outcome::DefenderSurvived;
+    let mut tick: u32 = 0;
+    while tick < ROUND_TICKS {
+        round.step(tick);
+        if let Some(end) = round.finished(victory) {
+            outcome = end;
+            break;
+        }
+        tick = advance(&mut round, tick, &boundaries);
+    }
+
+    let total_threats = round.threats.len() as u32;
+    let threats_cured = round.threats_cured;
+    let systems_destroyed = round.systems_destroyed;
+
+    // The round lasted through the tick it ended on.
+    let duration_ms = ((tick + 1) * TICK_MS).min(ROUND_DURATION_MS);
+
+    validate_lock_in(action_log, &round.threats, duration_ms)?;
+
+    // ── Compute final score ───────────────────────────────────────────────────
+    // Only a defender-side finish earns a time bonus; losing quickly must not
+    // outscore losing slowly.
+    let scored_duration_ms = if outcome.attacker_won() {
+        ROUND_DURATION_MS
+    } else {
+        duration_ms
+    };
+    let score = calculate_score(
+        threats_cured,
+        total_threats,
+        systems_destroyed,
+        scored_duration_ms,
+    );
+    let accuracy_bps = threats_cured * 10_000 / total_threats.max(1);
+
+    let data_leaked_x100 = data_leaked_x100(round.data_leaked_ticks, total_threats);
+
+    Ok(GameJournal {
+        challenge_id,
+        player_pubkey,
+        bot_config_id,
+        threats_cured,
+        systems_destroyed,
+        data_leaked_x100,
+        score,
+        duration_ms,
+        accuracy_bps,
+        outcome,
+        systems_lost_mask: round.systems_lost_mask,
+    })
+}
+
+// ─── Hex encode (no_std compatible) ──────────────────────────────────────────
+
+fn hex_encode(bytes: &[u8]) -> String {
+    const HEX: &[u8] = b"0123456789abcdef";
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push(HEX[(b >> 4) as usize] as char);
+        s.push(HEX[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threats::cure_speed;
+    use alloc::string::ToString;
+
+    const PLAYER: [u8; 32] = [0x3b; 32];
+
+    fn every_tick(_round: &mut Round, tick: u32, _boundaries: &[u32]) -> u32 {
+        tick + 1
+    }
+
+    fn bot(
+        victory: &str,
+        multiplier: u32,
+        spawn: &str,
+        abilities: &[&str],
+        attack: &str,
+    ) -> BotConfigZK {
+        BotConfigZK {
+            bot_type: "worm".to_string(),
+            primary_target: "network".to_string(),
+            secondary_targets: vec!["api".to_string(), "auth".to_string()],
+            damage_multiplier: multiplier,
+            threat_count: 6,
+            spawn_pattern: spawn.to_string(),
+            skill_diversity: "medium".to_string(),
+            victory_condition: victory.to_string(),
+            abilities: abilities.iter().map(|a| a.to_string()).collect(),
+            resource_attack: attack.to_string(),
+        }
+    }
+
+    /// No defence, a dev held on each threat, the best-matching dev held on
+    /// each threat, and quick swaps before a dev settles in. Held devs reach
+    /// into Replication children's indices.
+    fn action_logs(config: &BotConfigZK) -> Vec<Vec<ActionEntry>> {
+        let threats = generate_threats(config, &round_seed(&PLAYER, &config.bot_type));
+        let held = if config.resource_attack() == ResourceAttack::Memory {
+            MEMORY_MAX_ASSIGNMENTS
+        } else {
+            DEV_SKILLS.len()
+        };
+        let entry = |dev: usize, threat: usize, from: u32, until: u32| ActionEntry {
+            dev_index: dev as u32,
+            threat_index: threat as u32,
+            assigned_at_ms: from,
+            unassigned_at_ms: until,
+        };
+
+        let hold = (0..held.min(config.max_threats()))
+            .map(|ti| {
+                let from = threats.get(ti).map_or(60_000, |t| t.spawn_time_ms + 200);
+                entry(ti, ti, from, ROUND_DURATION_MS)
+            })
+            .collect();
+        let mut free: Vec<usize> = (0..DEV_SKILLS.len()).collect();
+        let matched = threats
+            .iter()
+            .enumerate()
+            .take(held)
+            .map(|(ti, t)| {
+                let best = (0..free.len())
+                    .max_by_key(|&i| cure_speed(&t.required_skill_indices, free[i]))
+                    .unwrap();
+                entry(free.remove(best), ti, t.spawn_time_ms, ROUND_DURATION_MS)
+            })
+            .collect();
+        let swaps = threats
+            .iter()
+            .enumerate()
+            .take(held / 2)
+            .flat_map(|(ti, t)| {
+                let at = t.spawn_time_ms + 200;
+                [
+                    entry(2 * ti, ti, at, at + 500),
+                    entry(2 * ti + 1, ti, at + 600, ROUND_DURATION_MS),
+                ]
+            })
+            .collect();
+        vec![Vec::new(), hold, matched, swaps]
+    }
+
+    #[test]
+    fn event_driven_round_matches_tick_by_tick() {
+        let ability_sets: [&[&str]; 7] = [
+            &[],
+            &["stealth"],
+            &["mutation"],
+            &["replication"],
+            &["encryption"],
+            &["persistence"],
+            &[
+                "stealth",
+                "mutation",
+                "replication",
+                "encryption",
+                "persistence",
+            ],
+        ];
+        let mut rounds = 0;
+        for victory in ["time-survival", "system-destruction", "data-exfiltration"] {
+            for multiplier in [50, 100, 200] {
+                for spawn in ["steady", "burst", "crescendo"] {
+                    for abilities in ability_sets {
+                        for attack in ["none", "cpu", "memory", "bandwidth", "disk"] {
+                            let config = bot(victory, multiplier, spawn, abilities, attack);
+                            for log in action_logs(&config) {
+                                let fast = play_round(1, PLAYER, 1, &config, &log);
+                                let slow = play_round_with(1, PLAYER, 1, &config, &log, every_tick);
+                                assert_eq!(fast, slow, "{:?} with {:?}", config, log);
+                                rounds += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(rounds, 3 * 3 * 3 * 7 * 5 * 4);
+    }
+}
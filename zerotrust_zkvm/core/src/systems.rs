@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use crate::config::BotConfigZK;
+
+// ─── Target systems ──────────────────────────────────────────────────────────
+
+// Must match SystemTargetId order in contracts/bot-nft exactly — the journal's
+// systems_lost_mask uses these indices as bit positions.
+pub const SYSTEM_IDS: [&str; 11] = [
+    "compute",
+    "storage",
+    "network",
+    "auth",
+    "analytics",
+    "communication",
+    "transaction",
+    "api",
+    "endpoint",
+    "cdn",
+    "iot",
+];
+
+// × 1000 fixed-point, like threat damage. A secondary system outlasts the drain
+// of one threat (100_000) and falls to its failure hit, on the tick the FE counts
+// the system as destroyed; the primary target falls sooner.
+pub const SYSTEM_HEALTH: u32 = 150_000;
+pub const PRIMARY_DAMAGE_PCT: u32 = 200; // primary target takes double damage
+pub const SECONDARY_DAMAGE_PCT: u32 = 100;
+pub const THREAT_FAILURE_DAMAGE: u32 = 50_000; // lump hit when a threat fails
+
+pub fn system_index(tag: &str) -> Option<usize> {
+    SYSTEM_IDS.iter().position(|id| *id == tag)
+}
+
+/// A system under attack this round, with its own health pool.
+pub struct System {
+    pub id: usize, // index into SYSTEM_IDS
+    pub damage_pct: u32,
+    pub health: u32,
+    pub is_lost: bool,
+}
+
+impl System {
+    fn new(id: usize, damage_pct: u32) -> Self {
+        System {
+            id,
+            damage_pct,
+            health: SYSTEM_HEALTH,
+            is_lost: false,
+        }
+    }
+
+    /// Damage this system takes from a hit of `amount`.
+    pub fn scaled(&self, amount: u32) -> u32 {
+        amount * self.damage_pct / 100
+    }
+
+    /// Applies damage scaled by this system's weight. Returns true only on the
+    /// call that takes the system down.
+    pub fn take_damage(&mut self, amount: u32) -> bool {
+        if self.is_lost {
+            return false;
+        }
+        self.health = self.health.saturating_sub(self.scaled(amount));
+        self.is_lost = self.health == 0;
+        self.is_lost
+    }
+}
+
+/// Systems the bot attacks: the primary target first, then each distinct
+/// secondary target. Unknown tags are skipped; a bot with no valid targets
+/// attacks compute.
+pub fn target_systems(config: &BotConfigZK) -> Vec<System> {
+    let mut systems: Vec<System> = Vec::new();
+    if let Some(id) = system_index(&config.primary_target) {
+        systems.push(System::new(id, PRIMARY_DAMAGE_PCT));
+    }
+    for tag in &config.secondary_targets {
+        if let Some(id) = system_index(tag) {
+            if !systems.iter().any(|s| s.id == id) {
+                systems.push(System::new(id, SECONDARY_DAMAGE_PCT));
+            }
+        }
+    }
+    if systems.is_empty() {
+        systems.push(System::new(0, PRIMARY_DAMAGE_PCT));
+    }
+    systems
+}
+
+/// Iterates the SYSTEM_IDS named by a journal's systems_lost_mask.
+pub fn lost_system_ids(mask: u32) -> impl Iterator<Item = &'static str> {
+    SYSTEM_IDS
+        .iter()
+        .enumerate()
+        .filter(move |(i, _)| mask & (1 << i) != 0)
+        .map(|(_, id)| *id)
+}
@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use crate::abilities::{Abilities, ENCRYPTION_CURE_PCT, MUTATION_AFTER_MS, STEALTH_DELAY_MS};
+use crate::config::{
+    BotConfigZK, ResourceAttack, BASE_DAMAGE_RATE, CPU_CURE_PCT, DEV_SKILLS, DISK_DAMAGE_PCT,
+    SKILL_POOL,
+};
+use crate::rng::Rng;
+use crate::systems::target_systems;
+
+pub struct Threat {
+    pub spawn_time_ms: u32,
+    pub required_skill_indices: Vec<usize>, // indices into SKILL_POOL
+    pub damage_rate: u32,                   // per tick × 1000 (fixed-point)
+    pub current_damage: u32,
+    pub cure_progress: u32,
+    pub assigned_dev: Option<usize>,
+    pub is_cured: bool,
+    pub is_failed: bool,
+    /// Later than spawn_time_ms under Stealth.
+    pub visible_at_ms: u32,
+    /// (at_ms, new skills) under Mutation; taken once applied.
+    pub mutation: Option<(u32, Vec<usize>)>,
+    pub cured_at_ms: u32,
+    /// [from, until) periods the threat was cured or failed; until is
+    /// u32::MAX while it still is. Used to check the lock-in rule.
+    pub resolved_spans: Vec<(u32, u32)>,
+    /// Persistence re-opens a cured threat at most once.
+    pub reopened: bool,
+    /// Spawned by Replication; children don't replicate.
+    pub is_child: bool,
+    /// Replication has already spawned this threat's child.
+    pub replicated: bool,
+    /// Index into the round's target_systems; threats cycle through them.
+    pub system: usize,
+}
+
+impl Threat {
+    fn new(
+        spawn_time_ms: u32,
+        required_skill_indices: Vec<usize>,
+        damage_rate: u32,
+        system: usize,
+    ) -> Self {
+        Threat {
+            spawn_time_ms,
+            visible_at_ms: spawn_time_ms,
+            required_skill_indices,
+            mutation: None,
+            damage_rate,
+            current_damage: 0,
+            cure_progress: 0,
+            assigned_dev: None,
+            is_cured: false,
+            is_failed: false,
+            cured_at_ms: 0,
+            resolved_spans: Vec::new(),
+            reopened: false,
+            is_child: false,
+            replicated: false,
+            system,
+        }
+    }
+
+    /// Neither curable nor able to deal damage for the rest of the round.
+    /// A cured threat that Persistence can still re-open doesn't count.
+    pub fn is_resolved(&self, abilities: Abilities) -> bool {
+        self.is_failed || (self.is_cured && (self.reopened || !abilities.persistence))
+    }
+
+    /// True if the threat was cured or failed at `at_ms`.
+    pub fn resolved_at(&self, at_ms: u32) -> bool {
+        self.resolved_spans
+            .iter()
+            .any(|&(from, until)| from <= at_ms && at_ms < until)
+    }
+
+    /// True while Replication can still spawn a child from this threat.
+    pub fn can_replicate(&self, abilities: Abilities) -> bool {
+        abilities.replication && !self.is_child && !self.replicated
+    }
+
+    /// Child spawned by Replication once this threat is half damaged.
+    pub fn replicate(&self, spawn_time_ms: u32, abilities: Abilities) -> Self {
+        let mut child = Threat::new(
+            spawn_time_ms,
+            self.required_skill_indices.clone(),
+            self.damage_rate,
+            self.system,
+        );
+        if abilities.stealth {
+            child.visible_at_ms = spawn_time_ms + STEALTH_DELAY_MS;
+        }
+        child.is_child = true;
+        child
+    }
+}
+
+fn shuffled_skills(rng: &mut Rng, skill_count: usize) -> Vec<usize> {
+    // Fisher-Yates shuffle then take first skill_count
+    let mut indices: Vec<usize> = (0..SKILL_POOL.len()).collect();
+    for j in (1..indices.len()).rev() {
+        let k = rng.next_usize(j + 1);
+        indices.swap(j, k);
+    }
+    indices.truncate(skill_count);
+    indices
+}
+
+// ─── Threat generation — mirrors generateThreatsFromBot ──────────────────────
+
+pub fn get_spawn_interval_ms(pattern: &str) -> u32 {
+    match pattern {
+        "steady" => 8_000,
+        "burst" => 3_000,
+        "crescendo" => 12_000,
+        _ => 8_000,
+    }
+}
+
+pub fn get_skill_count(diversity: &str) -> usize {
+    match diversity {
+        "low" => 2,
+        "medium" => 3,
+        "high" => 4,
+        _ => 3,
+    }
+}
+
+pub fn generate_threats(config: &BotConfigZK, seed: &str) -> Vec<Threat> {
+    let mut rng = Rng::new(seed);
+    let mut threats = Vec::new();
+    let interval_ms = get_spawn_interval_ms(&config.spawn_pattern);
+    let skill_count = get_skill_count(&config.skill_diversity);
+    let system_count = target_systems(config).len();
+
+    for i in 0..config.threat_count as usize {
+        let spawn_time_ms = if config.spawn_pattern == "crescendo" {
+            // mirrors: getSpawnInterval("crescendo") * i / (1 + i * 0.15)
+            // × 1000 fixed-point: interval * i * 1000 / (1000 + i * 150)
+            let numer = interval_ms * i as u32;
+            let denom = 1000 + i as u32 * 150;
+            numer * 1000 / denom.max(1)
+        } else {
+            interval_ms * i as u32
+        };
+
+        let required_skill_indices = shuffled_skills(&mut rng, skill_count);
+
+        // FE adds damageRate / 10 percent per tick, damageRate = 1.2 × damageMultiplier
+        // × 1000 fixed-point: damage_multiplier / 100 × BASE_DAMAGE_RATE / 10 / 10 × 1000
+        let mut damage_rate = config.damage_multiplier * BASE_DAMAGE_RATE / 10;
+        if config.resource_attack() == ResourceAttack::Disk {
+            damage_rate = damage_rate * DISK_DAMAGE_PCT / 100;
+        }
+
+        threats.push(Threat::new(
+            spawn_time_ms,
+            required_skill_indices,
+            damage_rate,
+            i % system_count,
+        ));
+    }
+
+    // Ability effects are drawn after every base threat, so bots without
+    // abilities keep the exact RNG sequence of the original generator.
+    let abilities = config.abilities();
+    for threat in threats.iter_mut() {
+        if abilities.stealth {
+            threat.visible_at_ms = threat.spawn_time_ms + STEALTH_DELAY_MS;
+        }
+        if abilities.mutation {
+            let skills = shuffled_skills(&mut rng, skill_count);
+            threat.mutation = Some((threat.spawn_time_ms + MUTATION_AFTER_MS, skills));
+        }
+    }
+
+    threats
+}
+
+// ─── Cure speed — mirrors calculateCureSpeed ─────────────────────────────────
+// Returns cure rate × 1000 per tick
+
+pub fn cure_speed(threat_skills: &[usize], dev_index: usize) -> u32 {
+    let dev = &DEV_SKILLS[dev_index];
+    let match_count = threat_skills.iter().filter(|&&s| dev.contains(&s)).count();
+    let total = threat_skills.len();
+    let ratio_x3 = match_count * 3 / total; // 0, 1, 2, or 3
+
+    // BASE_CURE_RATES: [30, 20, 10, 5] → rates × 10
+    // per tick (100ms): rate / 10 / 10 = rate / 100
+    // × 1000 fixed point: rate * 10
+    match ratio_x3 {
+        3 => 300, // 3.0 per second → 30 per tick × 10
+        2 => 200, // 2.0
+        1 => 100, // 1.0
+        _ => 50,  // 0.5
+    }
+}
+
+/// cure_speed with the bot's Encryption ability and Cpu resource attack applied.
+pub fn effective_cure_speed(
+    threat_skills: &[usize],
+    dev_index: usize,
+    abilities: Abilities,
+    resource_attack: ResourceAttack,
+) -> u32 {
+    let mut rate = cure_speed(threat_skills, dev_index);
+    if abilities.encryption {
+        rate = rate * ENCRYPTION_CURE_PCT / 100;
+    }
+    if resource_attack == ResourceAttack::Cpu {
+        rate = rate * CPU_CURE_PCT / 100;
+    }
+    rate
+}
@@ -0,0 +1,25 @@
+//! Bot abilities take effect under the bot-nft presets that carry them.
+
+mod common;
+
+use common::{bot, defend, play};
+use zerotrust_core::Outcome;
+
+#[test]
+fn replication_spawns_a_child_from_a_half_damaged_threat() {
+    // Worm: 0.8× damage, so no threat fails within the round
+    let mut config = bot("time-survival", 80, 3, &["compute", "storage", "network"]);
+    config.skill_diversity = "medium".into();
+    // Threat 0 is left alone and passes 50% damage at 52.1s
+    let log = defend(&config, &[1, 2]);
+
+    let without = play(&config, &log);
+    assert_eq!(without.threats_cured, 2);
+    assert_eq!(without.accuracy_bps, 6_666); // 2 of 3
+
+    config.abilities = vec!["replication".into()];
+    let with = play(&config, &log);
+    assert_eq!(with.outcome, Outcome::DefenderSurvived);
+    assert_eq!(with.threats_cured, 2);
+    assert_eq!(with.accuracy_bps, 5_000); // 2 of 4: the child counts too
+}
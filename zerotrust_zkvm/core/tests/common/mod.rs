@@ -0,0 +1,84 @@
+//! Bot configs and action logs shared by the round tests.
+
+#![allow(dead_code)]
+
+use zerotrust_core::{
+    cure_speed, generate_threats, play_round, round_seed, ActionEntry, BotConfigZK, GameJournal,
+    InvalidActionLog, Threat, DEV_SKILLS, ROUND_DURATION_MS,
+};
+
+pub const PLAYER: [u8; 32] = [0x3b; 32];
+
+/// A steady-spawning bot with no abilities or resource attack. The first
+/// target is the primary one.
+pub fn bot(
+    victory: &str,
+    damage_multiplier: u32,
+    threat_count: u32,
+    targets: &[&str],
+) -> BotConfigZK {
+    BotConfigZK {
+        bot_type: "malware".into(),
+        primary_target: targets[0].into(),
+        secondary_targets: targets[1..].iter().map(|t| t.to_string()).collect(),
+        damage_multiplier,
+        threat_count,
+        spawn_pattern: "steady".into(),
+        skill_diversity: "low".into(),
+        victory_condition: victory.into(),
+        abilities: vec![],
+        resource_attack: "none".into(),
+    }
+}
+
+pub fn play(config: &BotConfigZK, action_log: &[ActionEntry]) -> GameJournal {
+    try_play(config, action_log).expect("valid action log")
+}
+
+pub fn try_play(
+    config: &BotConfigZK,
+    action_log: &[ActionEntry],
+) -> Result<GameJournal, InvalidActionLog> {
+    play_round(1, PLAYER, 1, config, action_log)
+}
+
+/// The threats the round starts with, before any tick is played.
+pub fn threats(config: &BotConfigZK) -> Vec<Threat> {
+    generate_threats(config, &round_seed(&PLAYER, &config.bot_type))
+}
+
+pub fn assign(dev: u32, threat: u32, from_ms: u32, until_ms: u32) -> ActionEntry {
+    ActionEntry {
+        dev_index: dev,
+        threat_index: threat,
+        assigned_at_ms: from_ms,
+        unassigned_at_ms: until_ms,
+    }
+}
+
+/// Puts the best-matching free dev on each of `threat_indices` as it spawns
+/// and keeps them there for the rest of the round.
+pub fn defend(config: &BotConfigZK, threat_indices: &[usize]) -> Vec<ActionEntry> {
+    let threats = threats(config);
+    let mut free: Vec<usize> = (0..DEV_SKILLS.len()).collect();
+    threat_indices
+        .iter()
+        .map(|&ti| {
+            let skills = &threats[ti].required_skill_indices;
+            let best = (0..free.len())
+                .max_by_key(|&i| cure_speed(skills, free[i]))
+                .unwrap();
+            ActionEntry {
+                dev_index: free.remove(best) as u32,
+                threat_index: ti as u32,
+                assigned_at_ms: threats[ti].spawn_time_ms,
+                unassigned_at_ms: ROUND_DURATION_MS,
+            }
+        })
+        .collect()
+}
+
+pub fn defend_everything(config: &BotConfigZK) -> Vec<ActionEntry> {
+    let all: Vec<usize> = (0..config.threat_count as usize).collect();
+    defend(config, &all)
+}
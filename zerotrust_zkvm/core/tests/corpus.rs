@@ -0,0 +1,90 @@
+//! The journals the bench corpus rounds commit, pinned. A change here means
+//! every earlier proof of these rounds is now decoded differently: update the
+//! encodings only for a deliberate rule change, together with the image ID.
+
+use serde::Deserialize;
+use zerotrust_core::{play_round, ActionEntry, BotConfigZK, GameJournal};
+
+const CORPUS: &str = include_str!("../../host/bench/corpus.json");
+
+// (case name, journal hex)
+const EXPECTED: [(&str, &str); 7] = [
+    (
+        "steady-no-actions",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da290100000000000000020000000000000000000000a4320100000000000100000003000000",
+    ),
+    (
+        "steady-hold",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da2901000000020000000000000000000000cc0f0000905f0100a00f00000000000000000000",
+    ),
+    (
+        "burst-swaps",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da2901000000010000000000000000000000b7060000905f0100820600000000000000000000",
+    ),
+    (
+        "memory-hold",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da290100000003000000000000000000000040270000c0c10000102700000300000000000000",
+    ),
+    (
+        "crescendo-abilities",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da290100000000000000000000000000000035000000905f0100000000000000000000000000",
+    ),
+    (
+        "replication-bandwidth",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da2901000000020000000000000000000000cc0f0000905f0100a00f00000000000000000000",
+    ),
+    (
+        "disk-destruction",
+        "010000003b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29010000000000000003000000040c0000000000006c200000000000000100000007000000",
+    ),
+];
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    challenge_id: u32,
+    player_pubkey: String,
+    bot_config_id: u32,
+    bot_config: BotConfigZK,
+    action_log: Vec<ActionEntry>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn player_pubkey(hex: &str) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    key
+}
+
+#[test]
+fn corpus_journals_are_unchanged() {
+    let cases: Vec<Case> = serde_json::from_str(CORPUS).unwrap();
+
+    let actual: Vec<(&str, String)> = cases
+        .iter()
+        .map(|case| {
+            let journal = play_round(
+                case.challenge_id,
+                player_pubkey(&case.player_pubkey),
+                case.bot_config_id,
+                &case.bot_config,
+                &case.action_log,
+            )
+            .unwrap_or_else(|e| panic!("{}: {}", case.name, e));
+            let encoded = journal.encode();
+            assert_eq!(GameJournal::decode(&encoded).unwrap(), journal);
+            (case.name.as_str(), hex(&encoded))
+        })
+        .collect();
+
+    let expected: Vec<(&str, String)> = EXPECTED
+        .iter()
+        .map(|&(name, journal)| (name, journal.to_string()))
+        .collect();
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,117 @@
+//! The swap window and lock-in rule: a dev can be taken off a threat within
+//! SWAP_WINDOW_MS of being assigned, and after that only once the threat has
+//! resolved or the round is over.
+
+mod common;
+
+use common::{assign, bot, defend_everything, play, threats, try_play};
+use zerotrust_core::{
+    cure_speed, BotConfigZK, InvalidActionLog, Outcome, PERSISTENCE_DELAY_MS, ROUND_DURATION_MS,
+    SWAP_WINDOW_MS, TICK_MS,
+};
+
+/// One threat, cured by dev 7 on the 49.9s tick when left on it.
+fn lone_threat() -> BotConfigZK {
+    let mut config = bot("time-survival", 100, 1, &["compute"]);
+    config.skill_diversity = "medium".into();
+    config
+}
+
+/// The tick the lone threat is cured on.
+fn cured_at_ms(config: &BotConfigZK) -> u32 {
+    let journal = play(config, &defend_everything(config));
+    assert_eq!(journal.outcome, Outcome::ThreatsResolved);
+    journal.duration_ms - TICK_MS
+}
+
+#[test]
+fn swapping_out_is_allowed_within_the_window() {
+    let config = lone_threat();
+    play(&config, &[assign(7, 0, 0, SWAP_WINDOW_MS)]);
+    play(
+        &config,
+        &[
+            assign(7, 0, 0, SWAP_WINDOW_MS),
+            assign(3, 0, SWAP_WINDOW_MS, ROUND_DURATION_MS),
+        ],
+    );
+}
+
+#[test]
+fn unassigning_a_locked_dev_is_rejected() {
+    let config = lone_threat();
+    let cured = cured_at_ms(&config);
+
+    for until in [SWAP_WINDOW_MS + 1, 10_000, cured - TICK_MS] {
+        assert_eq!(
+            try_play(&config, &[assign(7, 0, 0, until)]),
+            Err(InvalidActionLog::UnassignedWhileLocked),
+            "unassigned at {until}ms"
+        );
+    }
+}
+
+#[test]
+fn unassigning_after_the_threat_resolves_is_allowed() {
+    // Persistence keeps the round going after the cure
+    let mut config = lone_threat();
+    let cured = cured_at_ms(&config);
+    config.abilities = vec!["persistence".into()];
+
+    // Dev 7 is needed through the tick the cure lands on
+    play(&config, &[assign(7, 0, 0, cured + TICK_MS)]);
+    play(&config, &[assign(7, 0, 0, cured + 2 * TICK_MS)]);
+}
+
+#[test]
+fn unassigning_when_the_round_ends_is_allowed() {
+    let config = bot(
+        "system-destruction",
+        200,
+        4,
+        &["compute", "storage", "network", "api"],
+    );
+    // A dev who can't cure the last threat, so it is still active at the end
+    let last = &threats(&config)[3];
+    let dev = (0..8)
+        .min_by_key(|&dev| cure_speed(&last.required_skill_indices, dev))
+        .unwrap() as u32;
+    let on_last = |until| [assign(dev, 3, last.spawn_time_ms, until)];
+
+    let journal = play(&config, &on_last(ROUND_DURATION_MS));
+    assert_eq!(journal.outcome, Outcome::SystemsDestroyed);
+    let end = journal.duration_ms;
+    assert!(end < ROUND_DURATION_MS);
+
+    assert_eq!(play(&config, &on_last(end)), journal);
+    assert_eq!(
+        try_play(&config, &on_last(end - TICK_MS)),
+        Err(InvalidActionLog::UnassignedWhileLocked)
+    );
+}
+
+#[test]
+fn assigning_to_a_resolved_threat_is_rejected() {
+    let mut config = lone_threat();
+    let cured = cured_at_ms(&config);
+    config.abilities = vec!["persistence".into()];
+    let reopened = cured + PERSISTENCE_DELAY_MS;
+
+    // Dev 7 cures it, then dev 3 comes in while it is cured
+    let log = |dev_3_at: u32| {
+        [
+            assign(7, 0, 0, cured + TICK_MS),
+            assign(3, 0, dev_3_at, ROUND_DURATION_MS),
+        ]
+    };
+    for at in [cured + TICK_MS, reopened - TICK_MS] {
+        assert_eq!(
+            try_play(&config, &log(at)),
+            Err(InvalidActionLog::AssignedToResolvedThreat),
+            "assigned at {at}ms"
+        );
+    }
+
+    // Once Persistence re-opens it, it can be worked on again
+    play(&config, &log(reopened + TICK_MS));
+}
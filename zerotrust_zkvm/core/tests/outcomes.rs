@@ -0,0 +1,68 @@
+//! Every round outcome is reachable with bot configs inside the bot-nft
+//! bounds (damage_multiplier 50–200, threat_count 3–8).
+
+mod common;
+
+use common::{bot, defend_everything, play};
+use zerotrust_core::{system_index, Outcome, ROUND_DURATION_MS};
+
+#[test]
+fn weak_bot_is_survived() {
+    let config = bot("time-survival", 50, 3, &["compute", "storage", "network"]);
+    let journal = play(&config, &[]);
+
+    assert_eq!(journal.outcome, Outcome::DefenderSurvived);
+    assert_eq!(journal.duration_ms, ROUND_DURATION_MS);
+    assert!(journal.systems_destroyed < 3);
+}
+
+#[test]
+fn curing_every_threat_ends_the_round_early() {
+    let mut config = bot("time-survival", 100, 3, &["compute"]);
+    config.skill_diversity = "medium".into();
+    let journal = play(&config, &defend_everything(&config));
+
+    assert_eq!(journal.outcome, Outcome::ThreatsResolved);
+    assert_eq!(journal.threats_cured, 3);
+    assert_eq!(journal.accuracy_bps, 10_000);
+    assert!(journal.duration_ms < ROUND_DURATION_MS);
+}
+
+#[test]
+fn time_survival_is_lost_once_every_target_falls() {
+    let config = bot("time-survival", 100, 3, &["compute"]);
+    let journal = play(&config, &[]);
+
+    assert_eq!(journal.outcome, Outcome::SystemsDestroyed);
+    assert_eq!(journal.systems_destroyed, 1);
+    assert_eq!(
+        journal.systems_lost_mask,
+        1 << system_index("compute").unwrap()
+    );
+    assert!(journal.duration_ms < ROUND_DURATION_MS);
+}
+
+#[test]
+fn system_destruction_is_lost_after_three_systems() {
+    let config = bot(
+        "system-destruction",
+        200,
+        4,
+        &["compute", "storage", "network", "api"],
+    );
+    let journal = play(&config, &[]);
+
+    assert_eq!(journal.outcome, Outcome::SystemsDestroyed);
+    assert_eq!(journal.systems_destroyed, 3);
+    assert!(journal.duration_ms < ROUND_DURATION_MS);
+}
+
+#[test]
+fn data_exfiltration_is_lost_once_half_the_threats_leak() {
+    let config = bot("data-exfiltration", 200, 4, &["compute", "storage"]);
+    let journal = play(&config, &[]);
+
+    assert_eq!(journal.outcome, Outcome::DataExfiltrated);
+    assert!(journal.data_leaked_x100 >= 5_000);
+    assert!(journal.duration_ms < ROUND_DURATION_MS);
+}
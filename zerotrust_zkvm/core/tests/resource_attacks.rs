@@ -0,0 +1,63 @@
+//! A bot's resource attack debuffs the defender for the whole round.
+
+mod common;
+
+use common::{assign, bot, defend, defend_everything, play, threats, try_play};
+use zerotrust_core::{InvalidActionLog, ROUND_DURATION_MS};
+
+#[test]
+fn memory_caps_concurrent_assignments() {
+    let mut config = bot("time-survival", 100, 4, &["compute"]);
+    config.resource_attack = "memory".into();
+    let started = threats(&config)[2].spawn_time_ms;
+    let swap = started + 1_000;
+
+    // Three devs at work; the first is swapped out for dev 8 on the same threat
+    let log = |dev_8_from: u32| {
+        vec![
+            assign(0, 0, started, swap),
+            assign(1, 1, started, ROUND_DURATION_MS),
+            assign(2, 2, started, ROUND_DURATION_MS),
+            assign(8, 0, dev_8_from, ROUND_DURATION_MS),
+        ]
+    };
+
+    // Back to back: the freed slot is taken on the same millisecond
+    play(&config, &log(swap));
+    // A moment early, that is a fourth dev at once
+    assert_eq!(
+        try_play(&config, &log(swap - 1)),
+        Err(InvalidActionLog::TooManyConcurrentAssignments)
+    );
+
+    // Without the attack, four at once is fine
+    config.resource_attack = "none".into();
+    play(&config, &log(swap - 1));
+}
+
+#[test]
+fn memory_leaves_three_defenders_alone() {
+    let mut config = bot("time-survival", 100, 3, &["compute"]);
+    config.skill_diversity = "medium".into();
+    let log = defend_everything(&config);
+    let unhindered = play(&config, &log);
+
+    config.resource_attack = "memory".into();
+    assert_eq!(play(&config, &log), unhindered);
+}
+
+#[test]
+fn cpu_and_bandwidth_slow_the_cure() {
+    let mut config = bot("time-survival", 100, 3, &["compute"]);
+    config.skill_diversity = "medium".into();
+    let log = defend(&config, &[0, 1, 2]);
+    let unhindered = play(&config, &log);
+
+    for attack in ["cpu", "bandwidth"] {
+        config.resource_attack = attack.into();
+        let debuffed = play(&config, &log);
+        assert_eq!(debuffed.threats_cured, unhindered.threats_cured, "{attack}");
+        assert!(debuffed.duration_ms > unhindered.duration_ms, "{attack}");
+        assert!(debuffed.score < unhindered.score, "{attack}");
+    }
+}
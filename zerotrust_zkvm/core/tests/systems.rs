@@ -0,0 +1,27 @@
+//! Target systems lose health to unattended threats and to failures.
+
+mod common;
+
+use common::{bot, play};
+use zerotrust_core::{system_index, Outcome, SYSTEM_HEALTH, THREAT_FAILURE_DAMAGE, TICK_MS};
+
+#[test]
+fn secondary_system_falls_when_its_threat_fails() {
+    // Threat 0 hits compute (primary), threat 1 hits storage from 8s
+    let config = bot("system-destruction", 200, 2, &["compute", "storage"]);
+    let journal = play(&config, &[]);
+
+    // 240 per tick from its spawn tick on: threat 1 fails on its 417th tick.
+    // Storage has taken the same 100_080 by then and only the failure hit
+    // takes it down.
+    let damage_ticks = 100_000u32.div_ceil(240);
+    let fail_tick = 80 + damage_ticks - 1;
+    assert!(240 * damage_ticks < SYSTEM_HEALTH);
+    assert!(240 * damage_ticks + THREAT_FAILURE_DAMAGE >= SYSTEM_HEALTH);
+
+    assert_eq!(journal.outcome, Outcome::SystemsDestroyed);
+    assert_eq!(journal.systems_destroyed, 2);
+    assert_eq!(journal.duration_ms, (fail_tick + 1) * TICK_MS);
+    let lost = 1 << system_index("compute").unwrap() | 1 << system_index("storage").unwrap();
+    assert_eq!(journal.systems_lost_mask, lost);
+}
@@ -0,0 +1,255 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, VerifyingKey};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use host::sha256;
+
+use crate::jobs::unix_now;
+
+pub const TIMESTAMP_HEADER: &str = "X-Zero-Trust-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Zero-Trust-Signature";
+
+// SEP-53: wallets sign sha256(prefix || message), never the raw message,
+// so a signed request can't double as a signed transaction.
+const SIGNED_MESSAGE_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+// ─── Request signatures ───────────────────────────────────────────────────────
+
+/// Checks that a /prove request was signed by the key in its `player_pubkey`.
+///
+/// The client signs `"{timestamp}.{body}"` as a SEP-53 message, sends the
+/// unix-seconds timestamp in X-Zero-Trust-Timestamp and the base64 signature
+/// in X-Zero-Trust-Signature. Timestamps further than `max_skew_secs` from
+/// now are rejected, and each signature is consumed at most once: a verbatim
+/// retry of a request that already created a job is answered with that job,
+/// and any other reuse is rejected.
+pub struct RequestAuth {
+    required: bool,
+    max_skew_secs: u64,
+    /// Signatures already used, with when they stop being acceptable anyway.
+    seen: Mutex<HashMap<[u8; 64], u64>>,
+}
+
+/// A request whose signature checked out but hasn't been consumed yet.
+pub struct SignedRequest {
+    /// The signature and when it stops being acceptable anyway; None when
+    /// signatures aren't required.
+    signature: Option<([u8; 64], u64)>,
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    Missing(&'static str),
+    Malformed(String),
+    Stale,
+    Replayed,
+    BadSignature,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Missing(header) => write!(f, "missing {} header", header),
+            AuthError::Malformed(e) => write!(f, "malformed signature: {}", e),
+            AuthError::Stale => write!(f, "request timestamp is too old or in the future"),
+            AuthError::Replayed => write!(f, "request signature has already been used"),
+            AuthError::BadSignature => {
+                write!(f, "signature does not match player_pubkey and request body")
+            }
+        }
+    }
+}
+
+impl RequestAuth {
+    pub fn new(required: bool, max_skew_secs: u64) -> Self {
+        RequestAuth {
+            required,
+            max_skew_secs,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    /// Checks the signature without consuming it, so a retry can still be
+    /// matched to its job first. Passes unconditionally when signatures
+    /// aren't required.
+    pub fn verify(
+        &self,
+        player_pubkey: &[u8; 32],
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<SignedRequest, AuthError> {
+        if !self.required {
+            return Ok(SignedRequest { signature: None });
+        }
+
+        let timestamp = timestamp.ok_or(AuthError::Missing(TIMESTAMP_HEADER))?;
+        let signature = signature.ok_or(AuthError::Missing(SIGNATURE_HEADER))?;
+
+        let signed_at: u64 = timestamp
+            .parse()
+            .map_err(|_| AuthError::Malformed(format!("bad timestamp {:?}", timestamp)))?;
+        let now = unix_now();
+        if signed_at.abs_diff(now) > self.max_skew_secs {
+            return Err(AuthError::Stale);
+        }
+
+        let signature: [u8; 64] = STANDARD
+            .decode(signature)
+            .map_err(|e| AuthError::Malformed(e.to_string()))?
+            .try_into()
+            .map_err(|_| AuthError::Malformed("signature must be 64 bytes".to_string()))?;
+        let key = VerifyingKey::from_bytes(player_pubkey)
+            .map_err(|_| AuthError::Malformed("player_pubkey is not an ed25519 key".to_string()))?;
+
+        let mut message = Vec::with_capacity(timestamp.len() + 1 + body.len());
+        message.extend_from_slice(timestamp.as_bytes());
+        message.push(b'.');
+        message.extend_from_slice(body);
+        key.verify_strict(
+            &signed_message_hash(&message),
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| AuthError::BadSignature)?;
+
+        Ok(SignedRequest {
+            signature: Some((signature, signed_at + self.max_skew_secs)),
+        })
+    }
+
+    /// Uses up a verified signature. Fails if it has been used before.
+    pub fn consume(&self, request: &SignedRequest) -> Result<(), AuthError> {
+        let Some((signature, expires)) = request.signature else {
+            return Ok(());
+        };
+
+        // Only a valid signature is remembered, so garbage can't fill the cache
+        let now = unix_now();
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, expires| *expires >= now);
+        if seen.insert(signature, expires).is_some() {
+            return Err(AuthError::Replayed);
+        }
+        Ok(())
+    }
+
+    /// Makes a consumed signature usable again, for a request that was
+    /// turned away after all and may be sent again unchanged.
+    pub fn release(&self, request: &SignedRequest) {
+        if let Some((signature, _)) = request.signature {
+            self.seen.lock().unwrap().remove(&signature);
+        }
+    }
+}
+
+fn signed_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut prefixed = SIGNED_MESSAGE_PREFIX.to_vec();
+    prefixed.extend_from_slice(message);
+    sha256(&prefixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const BODY: &[u8] = br#"{"bot_config_id":7}"#;
+    const MAX_SKEW_SECS: u64 = 300;
+
+    fn player() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    /// What a wallet sends for `body` signed at `timestamp`.
+    fn sign(key: &SigningKey, timestamp: &str, body: &[u8]) -> String {
+        let message = [timestamp.as_bytes(), b".", body].concat();
+        STANDARD.encode(key.sign(&signed_message_hash(&message)).to_bytes())
+    }
+
+    fn verify(
+        auth: &RequestAuth,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> Result<SignedRequest, AuthError> {
+        auth.verify(
+            &player().verifying_key().to_bytes(),
+            Some(timestamp),
+            Some(signature),
+            body,
+        )
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let auth = RequestAuth::new(true, MAX_SKEW_SECS);
+        let timestamp = unix_now().to_string();
+        let signature = sign(&player(), &timestamp, BODY);
+
+        let signed = verify(&auth, &timestamp, &signature, BODY).unwrap();
+        auth.consume(&signed).unwrap();
+    }
+
+    #[test]
+    fn rejects_stale_timestamp() {
+        let auth = RequestAuth::new(true, MAX_SKEW_SECS);
+        for timestamp in [
+            unix_now() - MAX_SKEW_SECS - 60,
+            unix_now() + MAX_SKEW_SECS + 60,
+        ] {
+            let timestamp = timestamp.to_string();
+            let signature = sign(&player(), &timestamp, BODY);
+            assert!(matches!(
+                verify(&auth, &timestamp, &signature, BODY),
+                Err(AuthError::Stale)
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_body() {
+        let auth = RequestAuth::new(true, MAX_SKEW_SECS);
+        let timestamp = unix_now().to_string();
+        let signature = sign(&player(), &timestamp, BODY);
+
+        let tampered = br#"{"bot_config_id":8}"#;
+        assert!(matches!(
+            verify(&auth, &timestamp, &signature, tampered),
+            Err(AuthError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn rejects_replayed_signature() {
+        let auth = RequestAuth::new(true, MAX_SKEW_SECS);
+        let timestamp = unix_now().to_string();
+        let signature = sign(&player(), &timestamp, BODY);
+
+        auth.consume(&verify(&auth, &timestamp, &signature, BODY).unwrap())
+            .unwrap();
+
+        // Still verifies, so a retry can be matched to its job, but only once
+        let replayed = verify(&auth, &timestamp, &signature, BODY).unwrap();
+        assert!(matches!(auth.consume(&replayed), Err(AuthError::Replayed)));
+    }
+
+    #[test]
+    fn released_signature_can_be_used_again() {
+        let auth = RequestAuth::new(true, MAX_SKEW_SECS);
+        let timestamp = unix_now().to_string();
+        let signature = sign(&player(), &timestamp, BODY);
+
+        let signed = verify(&auth, &timestamp, &signature, BODY).unwrap();
+        auth.consume(&signed).unwrap();
+        auth.release(&signed);
+
+        let retried = verify(&auth, &timestamp, &signature, BODY).unwrap();
+        auth.consume(&retried).unwrap();
+    }
+}
@@ -0,0 +1,234 @@
+// Cycle-count benchmark for the guest program.
+//
+// Runs every case in a corpus through the zkVM executor (no proving) and prints
+// a JSON report of cycles, segments and an estimated proving time per case.
+//
+//   cargo run --release --bin bench -- [corpus.json] [--prove-hz N]
+//       [--baseline report.json] [--max-regression-pct P]
+//
+// With --baseline, a case whose user cycles grew by more than P percent over
+// the baseline report fails the run.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use host::{execute_game, ActionEntry, BotConfigZK, ProveInput};
+
+// Rough proving throughput in padded cycles per second; calibrate against a
+// real proof on the target machine with --prove-hz.
+const DEFAULT_PROVE_HZ: u64 = 1_000_000;
+const DEFAULT_CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/bench/corpus.json");
+const DEFAULT_MAX_REGRESSION_PCT: u64 = 10;
+
+// ─── Corpus / report types ────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct BenchCase {
+    name: String,
+    challenge_id: u32,
+    player_pubkey: String, // hex, 32 bytes
+    bot_config_id: u32,
+    bot_config: BotConfigZK,
+    action_log: Vec<ActionEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CaseReport {
+    name: String,
+    action_log_len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_cycles: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_cycles: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segments: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_prove_secs: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outcome: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<u32>,
+    #[serde(default)]
+    execute_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchReport {
+    prove_hz: u64,
+    cases: Vec<CaseReport>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    regressions: Vec<String>,
+}
+
+struct Args {
+    corpus: String,
+    prove_hz: u64,
+    baseline: Option<String>,
+    max_regression_pct: u64,
+}
+
+// ─── Main ─────────────────────────────────────────────────────────────────────
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+
+    let corpus = std::fs::read_to_string(&args.corpus)
+        .with_context(|| format!("reading corpus {}", args.corpus))?;
+    let cases: Vec<BenchCase> =
+        serde_json::from_str(&corpus).with_context(|| format!("parsing corpus {}", args.corpus))?;
+
+    let mut report = BenchReport {
+        prove_hz: args.prove_hz,
+        cases: cases
+            .iter()
+            .map(|c| {
+                let report = run_case(c, args.prove_hz);
+                log_case(&report);
+                report
+            })
+            .collect(),
+        regressions: Vec::new(),
+    };
+
+    if let Some(path) = &args.baseline {
+        let baseline =
+            std::fs::read_to_string(path).with_context(|| format!("reading baseline {}", path))?;
+        let baseline: BenchReport = serde_json::from_str(&baseline)
+            .with_context(|| format!("parsing baseline {}", path))?;
+        report.regressions = regressions(&report, &baseline, args.max_regression_pct);
+    }
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    let failed = report.cases.iter().filter(|c| c.error.is_some()).count();
+    if failed > 0 || !report.regressions.is_empty() {
+        return Err(anyhow!(
+            "{} case(s) failed, {} regression(s)",
+            failed,
+            report.regressions.len()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        corpus: DEFAULT_CORPUS.to_string(),
+        prove_hz: DEFAULT_PROVE_HZ,
+        baseline: None,
+        max_regression_pct: DEFAULT_MAX_REGRESSION_PCT,
+    };
+
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = |flag: &str| it.next().ok_or_else(|| anyhow!("{} needs a value", flag));
+        match arg.as_str() {
+            "--prove-hz" => args.prove_hz = value("--prove-hz")?.parse()?,
+            "--baseline" => args.baseline = Some(value("--baseline")?),
+            "--max-regression-pct" => {
+                args.max_regression_pct = value("--max-regression-pct")?.parse()?
+            }
+            flag if flag.starts_with("--") => return Err(anyhow!("unknown flag {}", flag)),
+            path => args.corpus = path.to_string(),
+        }
+    }
+
+    if args.prove_hz == 0 {
+        return Err(anyhow!("--prove-hz must be positive"));
+    }
+    Ok(args)
+}
+
+// ─── Benchmark ────────────────────────────────────────────────────────────────
+
+fn run_case(case: &BenchCase, prove_hz: u64) -> CaseReport {
+    let mut report = CaseReport {
+        name: case.name.clone(),
+        action_log_len: case.action_log.len(),
+        user_cycles: None,
+        total_cycles: None,
+        segments: None,
+        estimated_prove_secs: None,
+        outcome: None,
+        score: None,
+        execute_ms: 0,
+        error: None,
+    };
+
+    let input = match prove_input(case) {
+        Ok(input) => input,
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let result = execute_game(&input);
+    report.execute_ms = start.elapsed().as_millis();
+
+    match result {
+        Ok(cycles) => {
+            report.user_cycles = Some(cycles.user_cycles);
+            report.total_cycles = Some(cycles.total_cycles);
+            report.segments = Some(cycles.segments);
+            report.estimated_prove_secs = Some(cycles.total_cycles as f64 / prove_hz as f64);
+            report.outcome = Some(cycles.journal.outcome.as_str().to_string());
+            report.score = Some(cycles.journal.score);
+        }
+        Err(e) => report.error = Some(e.to_string()),
+    }
+    report
+}
+
+fn prove_input(case: &BenchCase) -> Result<ProveInput> {
+    let player_pubkey: [u8; 32] = hex::decode(&case.player_pubkey)?
+        .try_into()
+        .map_err(|_| anyhow!("player_pubkey must be 32 bytes"))?;
+
+    Ok(ProveInput {
+        challenge_id: case.challenge_id,
+        player_pubkey,
+        bot_config_id: case.bot_config_id,
+        bot_config: case.bot_config.clone(),
+        action_log: case.action_log.clone(),
+    })
+}
+
+/// Cases whose user cycles grew past the allowed margin over the baseline.
+fn regressions(report: &BenchReport, baseline: &BenchReport, max_pct: u64) -> Vec<String> {
+    let before: HashMap<&str, u64> = baseline
+        .cases
+        .iter()
+        .filter_map(|c| Some((c.name.as_str(), c.user_cycles?)))
+        .collect();
+
+    report
+        .cases
+        .iter()
+        .filter_map(|c| {
+            let now = c.user_cycles?;
+            let was = *before.get(c.name.as_str())?;
+            (now * 100 > was * (100 + max_pct))
+                .then(|| format!("{}: user cycles {} -> {}", c.name, was, now))
+        })
+        .collect()
+}
+
+fn log_case(report: &CaseReport) {
+    match (&report.error, report.total_cycles) {
+        (Some(e), _) => eprintln!("{:<28} failed: {}", report.name, e),
+        (None, Some(total)) => eprintln!(
+            "{:<28} {:>10} cycles  {:>2} segments  {:>6}ms",
+            report.name,
+            total,
+            report.segments.unwrap_or(0),
+            report.execute_ms
+        ),
+        (None, None) => {}
+    }
+}
@@ -0,0 +1,115 @@
+use actix_cors::Cors;
+use anyhow::{anyhow, Context, Result};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use std::sync::Arc;
+
+use crate::env_or;
+
+const DEFAULT_BIND_ADDR: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 8080;
+const DEFAULT_MAX_BODY_BYTES: usize = 256 * 1024;
+// Succinct and composite receipts run to megabytes, more once base64-encoded
+const DEFAULT_MAX_VERIFY_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+// ─── HTTP server config ───────────────────────────────────────────────────────
+
+/// How the HTTP server listens, read from the environment (or `.env`):
+///
+/// - `BIND_ADDR` / `PORT` — listen address, default 0.0.0.0:8080
+/// - `CORS_ALLOWED_ORIGINS` — comma-separated origins, or `*` (default) for any
+/// - `HTTP_WORKERS` — actix worker threads, default one per physical core
+/// - `MAX_BODY_BYTES` — largest accepted request body, default 256 KiB
+/// - `MAX_VERIFY_BODY_BYTES` — largest accepted /verify body, default 16 MiB
+/// - `TLS_CERT_PATH` / `TLS_KEY_PATH` — PEM files; serve HTTPS when both are set
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub bind_addr: String,
+    pub port: u16,
+    /// None allows any origin.
+    pub cors_origins: Option<Vec<String>>,
+    pub workers: Option<usize>,
+    pub max_body_bytes: usize,
+    pub max_verify_body_bytes: usize,
+    pub tls: Option<TlsPaths>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TlsPaths {
+    pub cert: String,
+    pub key: String,
+}
+
+impl HttpConfig {
+    pub fn from_env() -> Result<Self> {
+        let origins = std::env::var("CORS_ALLOWED_ORIGINS").unwrap_or_else(|_| "*".to_string());
+        let cors_origins = match origins.trim() {
+            "*" => None,
+            list => Some(
+                list.split(',')
+                    .map(|o| o.trim().trim_end_matches('/').to_string())
+                    .filter(|o| !o.is_empty())
+                    .collect(),
+            ),
+        };
+
+        let tls = match (
+            std::env::var("TLS_CERT_PATH").ok(),
+            std::env::var("TLS_KEY_PATH").ok(),
+        ) {
+            (Some(cert), Some(key)) => Some(TlsPaths { cert, key }),
+            (None, None) => None,
+            _ => {
+                return Err(anyhow!(
+                    "TLS_CERT_PATH and TLS_KEY_PATH must be set together"
+                ))
+            }
+        };
+
+        Ok(HttpConfig {
+            bind_addr: std::env::var("BIND_ADDR").unwrap_or_else(|_| DEFAULT_BIND_ADDR.to_string()),
+            port: env_or("PORT", DEFAULT_PORT),
+            cors_origins,
+            workers: Some(env_or("HTTP_WORKERS", 0)).filter(|&w| w > 0),
+            max_body_bytes: env_or("MAX_BODY_BYTES", DEFAULT_MAX_BODY_BYTES),
+            max_verify_body_bytes: env_or("MAX_VERIFY_BODY_BYTES", DEFAULT_MAX_VERIFY_BODY_BYTES),
+            tls,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
+        format!("{}://{}:{}", scheme, self.bind_addr, self.port)
+    }
+
+    pub fn cors(&self) -> Cors {
+        let cors = match &self.cors_origins {
+            None => Cors::default().allow_any_origin(),
+            Some(origins) => origins
+                .iter()
+                .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin)),
+        };
+        cors.allow_any_method().allow_any_header().max_age(3600)
+    }
+
+    /// Loads the certificate chain and key, if TLS is configured.
+    pub fn rustls_config(&self) -> Result<Option<rustls::ServerConfig>> {
+        let Some(tls) = &self.tls else {
+            return Ok(None);
+        };
+
+        let certs = CertificateDer::pem_file_iter(&tls.cert)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .with_context(|| format!("reading TLS certificate {}", tls.cert))?;
+        let key = PrivateKeyDer::from_pem_file(&tls.key)
+            .with_context(|| format!("reading TLS key {}", tls.key))?;
+
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .context("TLS certificate and key don't match")?;
+        Ok(Some(config))
+    }
+}
@@ -0,0 +1,146 @@
+use actix_web::web::Bytes;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use host::ProveProgress;
+
+use crate::jobs::{JobState, JobStore};
+
+// Events buffered per subscriber before it starts lagging.
+pub const EVENT_BUFFER: usize = 256;
+
+// Sent on an idle stream so proxies don't time it out mid-proof.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+// ─── Job events ───────────────────────────────────────────────────────────────
+
+/// Something that happened to a job, broadcast to /status/{job_id}/events.
+#[derive(Debug, Clone)]
+pub struct JobEvent {
+    pub job_id: String,
+    pub kind: JobEventKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum JobEventKind {
+    State(JobState),
+    Progress(ProveProgress),
+}
+
+impl JobEvent {
+    pub fn state(job_id: &str, state: JobState) -> Self {
+        JobEvent {
+            job_id: job_id.to_string(),
+            kind: JobEventKind::State(state),
+        }
+    }
+
+    pub fn progress(job_id: &str, progress: ProveProgress) -> Self {
+        JobEvent {
+            job_id: job_id.to_string(),
+            kind: JobEventKind::Progress(progress),
+        }
+    }
+
+    /// Nothing more will happen to the job after this event.
+    pub fn is_final(&self) -> bool {
+        matches!(&self.kind, JobEventKind::State(state) if state.is_finished())
+    }
+
+    /// Encodes the event as one `text/event-stream` message: `event: state`
+    /// or `event: progress`, with the job_id and payload as JSON data.
+    pub fn to_sse(&self) -> Bytes {
+        #[derive(Serialize)]
+        struct Data<'a, T: Serialize> {
+            job_id: &'a str,
+            #[serde(flatten)]
+            body: &'a T,
+        }
+
+        let (event, data) = match &self.kind {
+            JobEventKind::State(state) => (
+                "state",
+                serde_json::to_string(&Data {
+                    job_id: &self.job_id,
+                    body: state,
+                }),
+            ),
+            JobEventKind::Progress(progress) => (
+                "progress",
+                serde_json::to_string(&Data {
+                    job_id: &self.job_id,
+                    body: progress,
+                }),
+            ),
+        };
+        let data = data.unwrap_or_else(|e| format!("{{\"error\":\"{}\"}}", e));
+        Bytes::from(format!("event: {}\ndata: {}\n\n", event, data))
+    }
+}
+
+// ─── Event stream ─────────────────────────────────────────────────────────────
+
+/// The events of one job, as an SSE body. Ends after the job finishes.
+pub struct EventStream {
+    job_id: String,
+    events: broadcast::Receiver<JobEvent>,
+    jobs: JobStore,
+    queued: VecDeque<JobEvent>,
+    done: bool,
+}
+
+impl EventStream {
+    /// `events` must be subscribed before `initial` is read, so nothing that
+    /// happens in between is missed.
+    pub fn new(
+        job_id: String,
+        events: broadcast::Receiver<JobEvent>,
+        jobs: JobStore,
+        initial: Vec<JobEvent>,
+    ) -> Self {
+        EventStream {
+            job_id,
+            events,
+            jobs,
+            queued: initial.into(),
+            done: false,
+        }
+    }
+
+    pub fn into_stream(
+        self,
+    ) -> impl futures_util::Stream<Item = Result<Bytes, actix_web::Error>> + 'static {
+        futures_util::stream::unfold(self, |mut stream| async move {
+            let chunk = stream.next_chunk().await?;
+            Some((Ok(chunk), stream))
+        })
+    }
+
+    async fn next_chunk(&mut self) -> Option<Bytes> {
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                self.done |= event.is_final();
+                return Some(event.to_sse());
+            }
+            if self.done {
+                return None;
+            }
+
+            match tokio::time::timeout(KEEPALIVE_INTERVAL, self.events.recv()).await {
+                Err(_) => return Some(Bytes::from_static(b": keepalive\n\n")),
+                Ok(Ok(event)) if event.job_id == self.job_id => self.queued.push_back(event),
+                Ok(Ok(_)) => {}
+                // Missed some events: the stored state is still accurate
+                Ok(Err(RecvError::Lagged(_))) => match self.jobs.get(&self.job_id) {
+                    Ok(Some(record)) => self
+                        .queued
+                        .push_back(JobEvent::state(&self.job_id, record.state)),
+                    _ => return None,
+                },
+                Ok(Err(RecvError::Closed)) => return None,
+            }
+        }
+    }
+}
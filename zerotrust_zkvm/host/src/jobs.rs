@@ -0,0 +1,351 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use host::{ProveInput, ProveResponse};
+
+// ─── Job state ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum JobState {
+    /// Queue fields are filled in live by /status, never stored.
+    #[serde(rename = "pending")]
+    Pending {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        queue_position: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        estimated_start_at: Option<u64>, // unix seconds
+    },
+    #[serde(rename = "proving")]
+    Proving,
+    #[serde(rename = "done")]
+    Done { result: Box<ProveResponse> },
+    #[serde(rename = "failed")]
+    Failed { error: String },
+    #[serde(rename = "cancelled")]
+    Cancelled,
+    /// A finished job whose result outlived the retention period.
+    #[serde(rename = "expired")]
+    Expired,
+}
+
+impl JobState {
+    /// Pending as stored, without live queue details.
+    pub fn pending() -> Self {
+        JobState::Pending {
+            queue_position: None,
+            estimated_start_at: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, JobState::Pending { .. } | JobState::Proving)
+    }
+
+    /// The `status` this state serializes with.
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobState::Pending { .. } => "pending",
+            JobState::Proving => "proving",
+            JobState::Done { .. } => "done",
+            JobState::Failed { .. } => "failed",
+            JobState::Cancelled => "cancelled",
+            JobState::Expired => "expired",
+        }
+    }
+}
+
+/// Every `JobState::name`.
+pub const STATE_NAMES: [&str; 6] = [
+    "pending",
+    "proving",
+    "done",
+    "failed",
+    "cancelled",
+    "expired",
+];
+
+/// What is persisted per job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub state: JobState,
+    /// Kept until the job finishes so it can be re-queued after a restart,
+    /// then dropped — the action log is the defender's private input.
+    pub input: Option<ProveInput>,
+    pub created_at: u64, // unix seconds
+    pub updated_at: u64,
+}
+
+// ─── Job store ────────────────────────────────────────────────────────────────
+
+/// Jobs keyed by job_id in an embedded sled database, so they survive a
+/// restart. Writes reach disk on `flush`, which callers run once they have
+/// released their own locks; the batch operations flush themselves.
+///
+/// Two indexes find the job a submission already created: by request digest,
+/// and by `{player_pubkey}:{Idempotency-Key}`. Finished proofs' encoded
+/// receipts are kept apart from the records, so /status stays small.
+#[derive(Clone)]
+pub struct JobStore {
+    db: sled::Db,
+    receipts: sled::Tree,
+    requests: sled::Tree,
+    idempotency_keys: sled::Tree,
+    /// Held from a submission's duplicate check until its job is reserved.
+    submitting: Arc<Mutex<()>>,
+}
+
+/// An Idempotency-Key's job and the digest of the request that first used it.
+#[derive(Serialize, Deserialize)]
+struct KeyedRequest {
+    job_id: String,
+    digest: String, // hex
+}
+
+impl JobStore {
+    pub fn open(path: &str) -> Result<Self> {
+        let db = sled::open(path).map_err(|e| anyhow!("opening job store {}: {}", path, e))?;
+        Ok(JobStore {
+            receipts: db.open_tree("receipts")?,
+            requests: db.open_tree("requests")?,
+            idempotency_keys: db.open_tree("idempotency_keys")?,
+            submitting: Arc::new(Mutex::new(())),
+            db,
+        })
+    }
+
+    pub fn get(&self, job_id: &str) -> Result<Option<JobRecord>> {
+        match self.db.get(job_id)? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records a pending job with its input, replacing its reservation.
+    pub fn create(&self, job_id: &str, input: &ProveInput) -> Result<()> {
+        let now = unix_now();
+        self.put(
+            job_id,
+            &JobRecord {
+                state: JobState::pending(),
+                input: Some(input.clone()),
+                created_at: now,
+                updated_at: now,
+            },
+        )
+    }
+
+    /// Records a pending job for a submission that is still being checked,
+    /// and indexes it so duplicates arriving meanwhile find it. `create` fills
+    /// in its input once it is queued. Call under `lock_submissions`.
+    pub fn reserve(
+        &self,
+        job_id: &str,
+        digest: &[u8; 32],
+        idempotency_key: Option<&str>,
+    ) -> Result<()> {
+        let now = unix_now();
+        self.put(
+            job_id,
+            &JobRecord {
+                state: JobState::pending(),
+                input: None,
+                created_at: now,
+                updated_at: now,
+            },
+        )?;
+        self.index_request(digest, idempotency_key, job_id)
+    }
+
+    /// Serializes submissions from their duplicate check to `reserve`, so of
+    /// two identical requests only one creates a job.
+    pub fn lock_submissions(&self) -> MutexGuard<'_, ()> {
+        self.submitting.lock().unwrap()
+    }
+
+    /// Moves a job to `state`, dropping its input once it has finished.
+    pub fn set_state(&self, job_id: &str, state: JobState) -> Result<()> {
+        let mut record = self
+            .get(job_id)?
+            .ok_or_else(|| anyhow!("job {} not found", job_id))?;
+        if state.is_finished() {
+            record.input = None;
+        }
+        record.state = state;
+        record.updated_at = unix_now();
+        self.put(job_id, &record)
+    }
+
+    /// Stores the encoded receipt of a finished proof.
+    pub fn put_receipt(&self, job_id: &str, receipt: &[u8]) -> Result<()> {
+        self.receipts.insert(job_id, receipt)?;
+        Ok(())
+    }
+
+    /// Persists every write so far. Blocks on disk I/O.
+    pub fn flush(&self) -> Result<()> {
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// The encoded receipt of a done job, until the job expires.
+    pub fn receipt(&self, job_id: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.receipts.get(job_id)?.map(|bytes| bytes.to_vec()))
+    }
+
+    /// How many stored jobs are in each state, by `JobState::name`.
+    pub fn count_by_state(&self) -> Result<HashMap<&'static str, usize>> {
+        let mut counts = HashMap::new();
+        for entry in self.db.iter() {
+            let (_, bytes) = entry?;
+            let record: JobRecord = serde_json::from_slice(&bytes)?;
+            *counts.entry(record.state.name()).or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Jobs that were pending or proving when the server last stopped, reset
+    /// to pending, with the input needed to run them again.
+    pub fn requeue_unfinished(&self) -> Result<Vec<(String, ProveInput)>> {
+        let mut requeued = Vec::new();
+        for entry in self.db.iter() {
+            let (key, bytes) = entry?;
+            let mut record: JobRecord = serde_json::from_slice(&bytes)?;
+            if record.state.is_finished() {
+                continue;
+            }
+
+            let job_id = String::from_utf8(key.to_vec())?;
+            match record.input.clone() {
+                Some(input) => {
+                    record.state = JobState::pending();
+                    record.updated_at = unix_now();
+                    self.put(&job_id, &record)?;
+                    requeued.push((job_id, input));
+                }
+                None => self.set_state(
+                    &job_id,
+                    JobState::Failed {
+                        error: "job input lost across restart".to_string(),
+                    },
+                )?,
+            }
+        }
+        self.db.flush()?;
+        Ok(requeued)
+    }
+
+    /// Reaps jobs that have been finished for longer than `ttl_secs`: their
+    /// result and receipt are dropped and they become Expired, so /status can still say
+    /// what happened. Expired jobs are removed after a further `ttl_secs`.
+    /// Returns how many jobs were expired and removed.
+    pub fn reap(&self, ttl_secs: u64) -> Result<(usize, usize)> {
+        let now = unix_now();
+        let cutoff = now.saturating_sub(ttl_secs);
+        let (mut expired, mut removed) = (0, 0);
+        for entry in self.db.iter() {
+            let (key, bytes) = entry?;
+            let mut record: JobRecord = serde_json::from_slice(&bytes)?;
+            if !record.state.is_finished() || record.updated_at >= cutoff {
+                continue;
+            }
+
+            if matches!(record.state, JobState::Expired) {
+                self.db.remove(key)?;
+                removed += 1;
+            } else {
+                record.state = JobState::Expired;
+                record.updated_at = now;
+                self.receipts.remove(&key)?;
+                self.db.insert(key, serde_json::to_vec(&record)?)?;
+                expired += 1;
+            }
+        }
+        if removed > 0 {
+            self.prune_indexes()?;
+        }
+        if expired + removed > 0 {
+            self.db.flush()?;
+        }
+        Ok((expired, removed))
+    }
+
+    /// The job last created for a request with this digest, if it still exists.
+    pub fn find_request(&self, digest: &[u8; 32]) -> Result<Option<(String, JobRecord)>> {
+        match self.requests.get(digest)? {
+            Some(job_id) => self.indexed_job(String::from_utf8(job_id.to_vec())?),
+            None => Ok(None),
+        }
+    }
+
+    /// The job created under an idempotency key, with the digest of the
+    /// request it was created for. `key` should be scoped to the player.
+    pub fn find_idempotency_key(&self, key: &str) -> Result<Option<([u8; 32], String, JobRecord)>> {
+        let Some(bytes) = self.idempotency_keys.get(key)? else {
+            return Ok(None);
+        };
+        let keyed: KeyedRequest = serde_json::from_slice(&bytes)?;
+        let digest: [u8; 32] = hex::decode(&keyed.digest)?
+            .try_into()
+            .map_err(|_| anyhow!("bad digest stored for idempotency key"))?;
+        Ok(self
+            .indexed_job(keyed.job_id)?
+            .map(|(job_id, record)| (digest, job_id, record)))
+    }
+
+    /// Points the request digest, and the idempotency key if any, at `job_id`.
+    pub fn index_request(
+        &self,
+        digest: &[u8; 32],
+        idempotency_key: Option<&str>,
+        job_id: &str,
+    ) -> Result<()> {
+        self.requests.insert(digest, job_id)?;
+        if let Some(key) = idempotency_key {
+            let keyed = KeyedRequest {
+                job_id: job_id.to_string(),
+                digest: hex::encode(digest),
+            };
+            self.idempotency_keys
+                .insert(key, serde_json::to_vec(&keyed)?)?;
+        }
+        Ok(())
+    }
+
+    fn indexed_job(&self, job_id: String) -> Result<Option<(String, JobRecord)>> {
+        Ok(self.get(&job_id)?.map(|record| (job_id, record)))
+    }
+
+    /// Drops index entries whose job has been removed.
+    fn prune_indexes(&self) -> Result<()> {
+        for entry in self.requests.iter() {
+            let (digest, job_id) = entry?;
+            if !self.db.contains_key(&job_id)? {
+                self.requests.remove(digest)?;
+            }
+        }
+        for entry in self.idempotency_keys.iter() {
+            let (key, bytes) = entry?;
+            let keyed: KeyedRequest = serde_json::from_slice(&bytes)?;
+            if !self.db.contains_key(&keyed.job_id)? {
+                self.idempotency_keys.remove(key)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn put(&self, job_id: &str, record: &JobRecord) -> Result<()> {
+        self.db.insert(job_id, serde_json::to_vec(record)?)?;
+        Ok(())
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -1,6 +1,12 @@
 use anyhow::{anyhow, Result};
-use risc0_zkvm::{default_prover, ExecutorEnv, InnerReceipt, ProverOpts, Receipt};
+use risc0_zkvm::{
+    default_executor, default_prover, get_prover_server, sha::Digestible, ExecutorEnv, ExecutorImpl, FakeReceipt,
+    Groth16Receipt, Groth16ReceiptVerifierParameters, InnerReceipt, ProverOpts, Receipt,
+    ReceiptClaim, Segment, SessionEvents, VerifierContext,
+};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::rc::Rc;
 
 // Generated by `cargo risczero build` — replace with your actual crate name
 use methods::{GUEST_ZEROTRUST_ZKVM_ELF, GUEST_ZEROTRUST_ZKVM_ID};
@@ -14,63 +20,17 @@ pub const TESTNET_CONTRACT_ID: &str =
 pub const TESTNET_NETWORK_PASSPHRASE: &str = "Test SDF Network ; September 2015";
 pub const TESTNET_RPC_URL: &str = "https://soroban-testnet.stellar.org";
 
-// ─── Journal layout ───────────────────────────────────────────────────────────
-// Matches what the guest program commits via env::commit_slice / env::commit
-//
-// Offset  Size  Field
-// 0       4     challenge_id         (u32 LE)
-// 4       32    player_pubkey        ([u8;32])
-// 36      4     bot_config_id        (u32 LE)
-// 40      4     threats_cured        (u32 LE)
-// 44      4     systems_destroyed    (u32 LE)
-// 48      4     data_leaked_x100     (u32 LE)  — e.g. 4550 = 45.50%
-// 52      4     score                (u32 LE)
-// 56      4     duration_ms          (u32 LE)
-// 60      4     accuracy_bps         (u32 LE)
-// ──────────────────────────────────────────────────────────────────────────────
-pub const JOURNAL_LEN: usize = 64;
+// ─── Shared game types ────────────────────────────────────────────────────────
+// Journal layout, bot config and action log types are owned by zerotrust-core,
+// which the guest links against too.
 
-// ─── Types ────────────────────────────────────────────────────────────────────
-
-/// Public outputs committed to by the guest — what gets posted on-chain.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct GameJournal {
-    pub challenge_id: u32,
-    pub player_pubkey: [u8; 32],
-    pub bot_config_id: u32,
-    pub threats_cured: u32,
-    pub systems_destroyed: u32,
-    pub data_leaked_x100: u32, // percentage × 100, e.g. 4550 = 45.50%
-    pub score: u32,
-    pub duration_ms: u32,
-    pub accuracy_bps: u32, // basis points, 10000 = 100%
-}
+pub use zerotrust_core::{ActionEntry, BotConfigZK, GameJournal, JOURNAL_LEN};
+use zerotrust_core::{lost_system_ids, play_round};
 
-/// Bot config fetched from Stellar and passed into the zkVM as a public input.
-/// All floats converted to fixed-point integers (×100) to match on-chain storage.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct BotConfigZK {
-    pub bot_type: String,           // e.g. "malware"
-    pub primary_target: String,     // e.g. "compute"
-    pub secondary_targets: Vec<String>,
-    pub damage_multiplier: u32,     // on-chain u32, e.g. 150 = 1.5×
-    pub threat_count: u32,
-    pub spawn_pattern: String,      // "steady" | "burst" | "crescendo"
-    pub skill_diversity: String,    // "low" | "medium" | "high"
-    pub victory_condition: String,  // "time_survival" | "system_destruction" | "data_exfiltration"
-}
-
-/// Action log entry — private input from defender.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct ActionEntry {
-    pub dev_index: u32,
-    pub threat_index: u32,
-    pub assigned_at_ms: u32,
-    pub unassigned_at_ms: u32,
-}
+// ─── Types ────────────────────────────────────────────────────────────────────
 
 /// Full input to the prover.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProveInput {
     pub challenge_id: u32,
     pub player_pubkey: [u8; 32],
@@ -80,13 +40,165 @@ pub struct ProveInput {
 }
 
 /// Everything the caller needs after proving.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProveResult {
     pub journal: GameJournal,
     pub journal_bytes: Vec<u8>,
     pub journal_sha256: [u8; 32],
+    /// Ready to submit on-chain: see `encode_seal`.
     pub seal: Vec<u8>,
     pub image_id: [u8; 32],
+    pub receipt_kind: ReceiptKind,
+    /// The full receipt, CBOR-encoded by `encode_receipt`.
+    pub receipt: Vec<u8>,
+    pub cycles: GuestCycles,
+}
+
+/// What running the guest cost, whichever prover ran it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GuestCycles {
+    /// Cycles the guest itself executed.
+    pub user_cycles: u64,
+    /// Cycles the prover pays for: each segment padded up to its power of two.
+    pub total_cycles: u64,
+    pub segments: usize,
+}
+
+/// Receipt the prover produces, selected with ZERO_TRUST_RECEIPT_KIND.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReceiptKind {
+    Groth16,
+    Succinct,
+    Composite,
+    /// Dev mode: the guest is executed but not proven. The journal is real,
+    /// the seal is empty and nothing verifies on-chain.
+    Fake,
+}
+
+impl ReceiptKind {
+    pub fn from_env() -> Self {
+        let kind = std::env::var("ZERO_TRUST_RECEIPT_KIND")
+            .unwrap_or_else(|_| "groth16".to_string())
+            .to_lowercase();
+
+        match kind.as_str() {
+            "succinct" => ReceiptKind::Succinct,
+            "composite" => ReceiptKind::Composite,
+            "fake" => ReceiptKind::Fake,
+            _ => ReceiptKind::Groth16,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReceiptKind::Groth16 => "groth16",
+            ReceiptKind::Succinct => "succinct",
+            ReceiptKind::Composite => "composite",
+            ReceiptKind::Fake => "fake",
+        }
+    }
+
+    pub fn is_fake(self) -> bool {
+        self == ReceiptKind::Fake
+    }
+}
+
+/// Milestones reported while a proof is being generated, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ProveProgress {
+    /// The executor has run the guest; proving proper starts now.
+    Executed {
+        user_cycles: u64,
+        total_cycles: u64,
+        segments: usize,
+    },
+    /// `segment` of `segments` has been proven (1-based).
+    SegmentProven { segment: u32, segments: usize },
+    /// Segment receipts are being lifted and joined into a succinct receipt.
+    Lifting,
+    /// The succinct receipt is being wrapped into a Groth16 proof.
+    Wrapping,
+}
+
+// ─── Responses ────────────────────────────────────────────────────────────────
+
+/// A finished proof as /status reports it and the job store keeps it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveResponse {
+    pub success: bool,
+    pub proof_verified: bool,
+    pub execution_time_ms: u128,
+    pub receipt_kind: String,
+    /// True for fake receipts — the result must not be submitted on-chain.
+    pub dev_mode: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seal_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_id_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_sha256_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_bytes_hex: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal: Option<JournalResponse>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalResponse {
+    pub challenge_id: u32,
+    pub player_pubkey: String,
+    pub bot_config_id: u32,
+    pub threats_cured: u32,
+    pub systems_destroyed: u32,
+    pub data_leaked_x100: u32,
+    pub score: u32,
+    pub duration_ms: u32,
+    pub accuracy_bps: u32,
+    pub attacker_won: bool,
+    pub outcome: String,
+    pub systems_lost: Vec<String>,
+}
+
+pub fn journal_response(j: &GameJournal) -> JournalResponse {
+    JournalResponse {
+        challenge_id: j.challenge_id,
+        player_pubkey: hex::encode(j.player_pubkey),
+        bot_config_id: j.bot_config_id,
+        threats_cured: j.threats_cured,
+        systems_destroyed: j.systems_destroyed,
+        data_leaked_x100: j.data_leaked_x100,
+        score: j.score,
+        duration_ms: j.duration_ms,
+        accuracy_bps: j.accuracy_bps,
+        attacker_won: j.outcome.attacker_won(),
+        outcome: j.outcome.as_str().to_string(),
+        systems_lost: lost_system_ids(j.systems_lost_mask)
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+pub fn prove_response(prove_result: &ProveResult, elapsed_ms: u128) -> ProveResponse {
+    ProveResponse {
+        success: true,
+        // A fake receipt was never proven, let alone verified
+        proof_verified: !prove_result.receipt_kind.is_fake(),
+        execution_time_ms: elapsed_ms,
+        receipt_kind: prove_result.receipt_kind.as_str().to_string(),
+        dev_mode: prove_result.receipt_kind.is_fake(),
+        seal_hex: Some(format!("0x{}", hex::encode(&prove_result.seal))),
+        image_id_hex: Some(hex::encode(prove_result.image_id)),
+        journal_sha256_hex: Some(hex::encode(prove_result.journal_sha256)),
+        journal_bytes_hex: Some(format!("0x{}", hex::encode(&prove_result.journal_bytes))),
+        journal: Some(journal_response(&prove_result.journal)),
+        error: None,
+    }
 }
 
 // ─── Stellar bot config fetching ─────────────────────────────────────────────
@@ -130,10 +242,10 @@ pub async fn fetch_bot_config_from_stellar(bot_config_id: u32) -> Result<BotConf
 /// This calls get_bot_config(token_id) on the NFT contract.
 fn build_simulate_tx(contract_id: &str, fn_name: &str, token_id: u32) -> Result<String> {
     use stellar_xdr::curr::{
-        ContractId, Hash, HostFunction, InvokeContractArgs, Limits, ScAddress, ScSymbol, ScVal,
-        ScVec, SorobanAuthorizationEntry, Transaction, TransactionEnvelope, TransactionExt,
+        ContractId, Hash, HostFunction, InvokeContractArgs, ScAddress, ScSymbol, ScVal,
+        Transaction, TransactionEnvelope, TransactionExt,
         TransactionV1Envelope, Uint256, Memo, MuxedAccount, Operation, OperationBody,
-        InvokeHostFunctionOp, WriteXdr, SequenceNumber, TimeBounds, TimePoint,
+        InvokeHostFunctionOp, WriteXdr, SequenceNumber,
     };
 
     // Decode contract address from strkey
@@ -192,7 +304,7 @@ fn parse_bot_config_from_rpc_response(
     resp: &serde_json::Value,
     token_id: u32,
 ) -> Result<BotConfigZK> {
-    use stellar_xdr::curr::{ReadXdr, ScVal, Limits};
+    use stellar_xdr::curr::{ReadXdr, ScVal};
 
     let result_b64 = resp
         .pointer("/result/results/0/xdr")
@@ -223,6 +335,8 @@ fn extract_bot_config_from_scval(val: &stellar_xdr::curr::ScVal) -> Result<BotCo
     let mut spawn_pattern = String::new();
     let mut skill_diversity = String::new();
     let mut victory_condition = String::new();
+    let mut abilities: Vec<String> = vec![];
+    let mut resource_attack = String::from("None");
 
     for entry in map.iter() {
         let key = match &entry.key {
@@ -250,6 +364,8 @@ fn extract_bot_config_from_scval(val: &stellar_xdr::curr::ScVal) -> Result<BotCo
             "spawn_pattern" => spawn_pattern = scval_enum_tag(&entry.val)?,
             "skill_diversity" => skill_diversity = scval_enum_tag(&entry.val)?,
             "victory_condition" => victory_condition = scval_enum_tag(&entry.val)?,
+            "abilities" => abilities = scval_vec_of_enum_tags(&entry.val)?,
+            "resource_attack" => resource_attack = scval_enum_tag(&entry.val)?,
             _ => {}
         }
     }
@@ -263,6 +379,8 @@ fn extract_bot_config_from_scval(val: &stellar_xdr::curr::ScVal) -> Result<BotCo
         spawn_pattern: normalise_tag(&spawn_pattern),
         skill_diversity: normalise_tag(&skill_diversity),
         victory_condition: normalise_tag_hyphen(&victory_condition),
+        abilities: abilities.into_iter().map(|s| normalise_tag(&s)).collect(),
+        resource_attack: normalise_tag(&resource_attack),
     })
 }
 
@@ -285,7 +403,7 @@ fn scval_vec_of_enum_tags(val: &stellar_xdr::curr::ScVal) -> Result<Vec<String>>
     match val {
         ScVal::Vec(Some(v)) => v.iter().map(scval_enum_tag).collect(),
         ScVal::Void => Ok(vec![]),
-        _ => Err(anyhow!("expected ScVal::Vec for secondary_targets")),
+        _ => Err(anyhow!("expected ScVal::Vec of enum tags")),
     }
 }
 
@@ -314,16 +432,27 @@ fn normalise_tag_hyphen(s: &str) -> String {
     result
 }
 
-// ─── Core prove function ──────────────────────────────────────────────────────
+// ─── Native simulation ────────────────────────────────────────────────────────
 
-pub fn prove_game(input: &ProveInput) -> Result<ProveResult> {
-        log::info!("Starting proof generation for bot_config_id={} player={}", 
+/// Runs the guest's round logic natively, without the zkVM. Produces the same
+/// journal `prove_game` would commit for this input, in milliseconds rather
+/// than minutes, so callers can predict the verified score before proving.
+pub fn simulate_round(input: &ProveInput) -> Result<GameJournal> {
+    play_round(
+        input.challenge_id,
+        input.player_pubkey,
         input.bot_config_id,
-        hex::encode(&input.player_pubkey[..4]) // first 4 bytes for brevity
-    );
+        &input.bot_config,
+        &input.action_log,
+    )
+    .map_err(|e| anyhow!("invalid action log: {}", e))
+}
 
+// ─── Guest execution ──────────────────────────────────────────────────────────
 
-    let env = ExecutorEnv::builder()
+/// Guest inputs, written in the order the guest reads them.
+fn executor_env(input: &ProveInput) -> Result<ExecutorEnv<'static>> {
+    ExecutorEnv::builder()
         // Public inputs
         .write(&input.challenge_id)?
         .write(&input.player_pubkey)?
@@ -331,31 +460,76 @@ pub fn prove_game(input: &ProveInput) -> Result<ProveResult> {
         .write(&input.bot_config)?
         // Private input — action log
         .write(&input.action_log)?
-        .build()?;
+        .build()
+}
+
+/// What running the guest costs, as measured by the executor.
+#[derive(Debug, Clone)]
+pub struct CycleReport {
+    pub journal: GameJournal,
+    /// Cycles the guest itself executed.
+    pub user_cycles: u64,
+    /// Cycles the prover pays for: each segment padded up to its power of two.
+    pub total_cycles: u64,
+    pub segments: usize,
+}
 
-          log::info!("Executor environment built, starting prover...");
+/// Runs the guest in the executor without proving — seconds rather than
+/// minutes. Fails if the guest panics, just as proving would.
+pub fn execute_game(input: &ProveInput) -> Result<CycleReport> {
+    let session = default_executor().execute(executor_env(input)?, GUEST_ZEROTRUST_ZKVM_ELF)?;
+    let journal = decode_journal(&session.journal.bytes)?;
 
-    let prover = default_prover();
-    let (opts, require_groth16) = prover_opts_from_env();
+    Ok(CycleReport {
+        journal,
+        user_cycles: session.cycles(),
+        total_cycles: session.segments.iter().map(|s| 1u64 << s.po2).sum(),
+        segments: session.segments.len(),
+    })
+}
 
-    let prove_info = prover.prove_with_opts(env, GUEST_ZEROTRUST_ZKVM_ELF, &opts)?;
-    
-    log::info!("Proof generated, verifying...");
-    prove_info.receipt.verify(GUEST_ZEROTRUST_ZKVM_ID)?;
- log::info!("Receipt verified successfully");
- 
-    let receipt = prove_info.receipt;
+// ─── Core prove function ──────────────────────────────────────────────────────
 
-    if require_groth16 && !matches!(&receipt.inner, InnerReceipt::Groth16(_)) {
-        return Err(anyhow!(
-            "expected Groth16 receipt; ensure Groth16 proving is enabled (Docker required)"
-        ));
-    }
+/// Proves with `default_prover()`, so `RISC0_PROVER` and Bonsai settings apply.
+pub fn prove_game(input: &ProveInput) -> Result<ProveResult> {
+    prove_game_inner(input, None)
+}
+
+/// `prove_game`, calling `on_progress` as each stage of proving completes.
+/// Stages can only be reported when `default_prover()` would prove in this
+/// process; any other prover it selects is used as is, reporting nothing.
+pub fn prove_game_with_progress(
+    input: &ProveInput,
+    on_progress: impl Fn(ProveProgress) + 'static,
+) -> Result<ProveResult> {
+    prove_game_inner(input, Some(Rc::new(on_progress)))
+}
+
+fn prove_game_inner(
+    input: &ProveInput,
+    on_progress: Option<Rc<dyn Fn(ProveProgress)>>,
+) -> Result<ProveResult> {
+        log::info!("Starting proof generation for bot_config_id={} player={}", 
+        input.bot_config_id,
+        hex::encode(&input.player_pubkey[..4]) // first 4 bytes for brevity
+    );
+
+    let receipt_kind = ReceiptKind::from_env();
+    let (receipt, cycles) = match (receipt_kind, on_progress) {
+        (ReceiptKind::Fake, on_progress) => {
+            log::warn!("ZERO_TRUST_RECEIPT_KIND=fake — executing only, no proof is generated");
+            fake_receipt(input, &*on_progress.unwrap_or_else(|| Rc::new(|_| {})))?
+        }
+        (_, Some(on_progress)) if default_prover().get_name() == "local" => {
+            prove_in_stages(input, receipt_kind, on_progress)?
+        }
+        _ => prove_receipt(input, receipt_kind)?,
+    };
 
     let journal_bytes = receipt.journal.bytes.clone();
     let journal = decode_journal(&journal_bytes)?;
     let journal_sha256 = sha256(&journal_bytes);
-    let seal = receipt_seal_bytes(&receipt)?;
+    let seal = encode_seal(&receipt)?;
     let image_id = digest_to_bytes(GUEST_ZEROTRUST_ZKVM_ID.into());
 
     Ok(ProveResult {
@@ -364,74 +538,243 @@ pub fn prove_game(input: &ProveInput) -> Result<ProveResult> {
         journal_sha256,
         seal,
         image_id,
+        receipt_kind,
+        receipt: encode_receipt(&receipt)?,
+        cycles,
     })
 }
 
-fn prover_opts_from_env() -> (ProverOpts, bool) {
-    let kind = std::env::var("ZERO_TRUST_RECEIPT_KIND")
-        .unwrap_or_else(|_| "groth16".to_string())
-        .to_lowercase();
+fn prove_receipt(input: &ProveInput, receipt_kind: ReceiptKind) -> Result<(Receipt, GuestCycles)> {
+    log::info!("Starting prover...");
+    let prover = default_prover();
+    let info = prover.prove_with_opts(
+        executor_env(input)?,
+        GUEST_ZEROTRUST_ZKVM_ELF,
+        &prover_opts(receipt_kind),
+    )?;
+    // Remote provers report what they ran, too
+    let cycles = GuestCycles {
+        user_cycles: info.stats.user_cycles,
+        total_cycles: info.stats.total_cycles,
+        segments: info.stats.segments,
+    };
+    Ok((check_receipt(info.receipt, receipt_kind)?, cycles))
+}
+
+/// Executes, proves the segments, then compresses step by step up to
+/// `receipt_kind` — the same pipeline the local `Prover::prove_with_opts`
+/// runs, taken apart so each stage can be reported.
+fn prove_in_stages(
+    input: &ProveInput,
+    receipt_kind: ReceiptKind,
+    on_progress: Rc<dyn Fn(ProveProgress)>,
+) -> Result<(Receipt, GuestCycles)> {
+    let mut session =
+        ExecutorImpl::from_elf(executor_env(input)?, GUEST_ZEROTRUST_ZKVM_ELF)?.run()?;
+    let segments = session.segments.len();
+    log::info!(
+        "Executed {} cycles in {} segment(s), starting prover...",
+        session.total_cycles,
+        segments
+    );
+    let cycles = GuestCycles {
+        user_cycles: session.user_cycles,
+        total_cycles: session.total_cycles,
+        segments,
+    };
+    on_progress(ProveProgress::Executed {
+        user_cycles: cycles.user_cycles,
+        total_cycles: cycles.total_cycles,
+        segments,
+    });
+    session.add_hook(SegmentProgress {
+        on_progress: on_progress.clone(),
+        segments,
+    });
+
+    // Composite first; anything smaller is compressed from it below
+    let prover = get_prover_server(&ProverOpts::composite())?;
+    let mut receipt = prover
+        .prove_session(&VerifierContext::default(), &session)?
+        .receipt;
 
-    match kind.as_str() {
-        "succinct" => (ProverOpts::succinct(), false),
-        "composite" => (ProverOpts::composite(), false),
-        "groth16" | _ => (ProverOpts::groth16(), true),
+    if receipt_kind != ReceiptKind::Composite {
+        on_progress(ProveProgress::Lifting);
+        receipt = prover.compress(&ProverOpts::succinct(), &receipt)?;
     }
+    if receipt_kind == ReceiptKind::Groth16 {
+        on_progress(ProveProgress::Wrapping);
+        receipt = prover.compress(&ProverOpts::groth16(), &receipt)?;
+    }
+    Ok((check_receipt(receipt, receipt_kind)?, cycles))
 }
 
-// ─── Journal encode / decode ──────────────────────────────────────────────────
+fn check_receipt(receipt: Receipt, receipt_kind: ReceiptKind) -> Result<Receipt> {
+    log::info!("Proof generated, verifying...");
+    receipt.verify(GUEST_ZEROTRUST_ZKVM_ID)?;
+ log::info!("Receipt verified successfully");
 
-pub fn decode_journal(bytes: &[u8]) -> Result<GameJournal> {
-    if bytes.len() != JOURNAL_LEN {
+    if receipt_kind == ReceiptKind::Groth16
+        && !matches!(&receipt.inner, InnerReceipt::Groth16(_))
+    {
         return Err(anyhow!(
-            "journal length mismatch: got {}, expected {}",
-            bytes.len(),
-            JOURNAL_LEN
+            "expected Groth16 receipt; ensure Groth16 proving is enabled (Docker required)"
         ));
     }
 
-    let mut o = 0usize;
+    Ok(receipt)
+}
 
-    macro_rules! read_u32 {
-        () => {{
-            let v = u32::from_le_bytes(bytes[o..o + 4].try_into().unwrap());
-            o += 4;
-            v
-        }};
+fn prover_opts(receipt_kind: ReceiptKind) -> ProverOpts {
+    match receipt_kind {
+        ReceiptKind::Succinct => ProverOpts::succinct(),
+        ReceiptKind::Composite => ProverOpts::composite(),
+        ReceiptKind::Groth16 | ReceiptKind::Fake => ProverOpts::groth16(),
     }
+}
+
+/// Reports each segment as the prover finishes it.
+struct SegmentProgress {
+    on_progress: Rc<dyn Fn(ProveProgress)>,
+    segments: usize,
+}
 
-    macro_rules! read_bytes32 {
-        () => {{
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&bytes[o..o + 32]);
-            o += 32;
-            arr
-        }};
+impl SessionEvents for SegmentProgress {
+    fn on_post_prove_segment(&self, segment: &Segment) {
+        (self.on_progress)(ProveProgress::SegmentProven {
+            segment: segment.index + 1,
+            segments: self.segments,
+        });
     }
+}
+
+/// Runs the executor only and wraps its claim in a FakeReceipt, so the rest of
+/// the pipeline sees a real journal without Docker or a GPU.
+fn fake_receipt(
+    input: &ProveInput,
+    on_progress: &dyn Fn(ProveProgress),
+) -> Result<(Receipt, GuestCycles)> {
+    let session = default_executor().execute(executor_env(input)?, GUEST_ZEROTRUST_ZKVM_ELF)?;
+    let cycles = GuestCycles {
+        user_cycles: session.cycles(),
+        total_cycles: session.segments.iter().map(|s| 1u64 << s.po2).sum(),
+        segments: session.segments.len(),
+    };
+    on_progress(ProveProgress::Executed {
+        user_cycles: cycles.user_cycles,
+        total_cycles: cycles.total_cycles,
+        segments: cycles.segments,
+    });
+    let claim = session
+        .receipt_claim
+        .ok_or_else(|| anyhow!("executor returned no receipt claim"))?;
+
+    let receipt = Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(claim)),
+        session.journal.bytes,
+    );
+    Ok((receipt, cycles))
+}
+
+// ─── Offline verification ─────────────────────────────────────────────────────
+
+// A Groth16 seal: the proof's three BN254 points, uncompressed.
+pub const GROTH16_SEAL_LEN: usize = 256;
 
-    let challenge_id = read_u32!();
-    let player_pubkey = read_bytes32!();
-    let bot_config_id = read_u32!();
-    let threats_cured = read_u32!();
-    let systems_destroyed = read_u32!();
-    let data_leaked_x100 = read_u32!();
-    let score = read_u32!();
-    let duration_ms = read_u32!();
-    let accuracy_bps = read_u32!();
-
-    Ok(GameJournal {
-        challenge_id,
-        player_pubkey,
-        bot_config_id,
-        threats_cured,
-        systems_destroyed,
-        data_leaked_x100,
-        score,
-        duration_ms,
-        accuracy_bps,
+/// A receipt or seal that verified, and the round it proves.
+#[derive(Debug, Clone)]
+pub struct VerifiedProof {
+    pub receipt_kind: ReceiptKind,
+    pub image_id: [u8; 32],
+    pub journal: GameJournal,
+    pub journal_bytes: Vec<u8>,
+    pub journal_sha256: [u8; 32],
+}
+
+/// Verifies a receipt against `image_id` and decodes its journal. Fake
+/// receipts prove nothing and are refused.
+pub fn verify_receipt(receipt: &Receipt, image_id: [u8; 32]) -> Result<VerifiedProof> {
+    let receipt_kind = match &receipt.inner {
+        InnerReceipt::Groth16(_) => ReceiptKind::Groth16,
+        InnerReceipt::Succinct(_) => ReceiptKind::Succinct,
+        InnerReceipt::Composite(_) => ReceiptKind::Composite,
+        InnerReceipt::Fake(_) => return Err(anyhow!("fake receipts are not proofs")),
+        _ => return Err(anyhow!("unsupported receipt type")),
+    };
+    receipt
+        .verify(risc0_zkvm::sha::Digest::from_bytes(image_id))
+        .map_err(|e| anyhow!("receipt does not verify: {}", e))?;
+
+    let journal_bytes = receipt.journal.bytes.clone();
+    Ok(VerifiedProof {
+        receipt_kind,
+        image_id,
+        journal: decode_journal(&journal_bytes)?,
+        journal_sha256: sha256(&journal_bytes),
+        journal_bytes,
     })
 }
 
+/// Verifies a Groth16 seal the way the on-chain verifier does: as a proof
+/// that `image_id` ran successfully and committed `journal_bytes`. The seal
+/// may be bare or, as `encode_seal` makes it, behind the verifier selector.
+pub fn verify_seal(seal: &[u8], journal_bytes: &[u8], image_id: [u8; 32]) -> Result<VerifiedProof> {
+    let seal = match seal.len() {
+        GROTH16_SEAL_LEN => seal,
+        len if len == GROTH16_SEAL_LEN + 4 => {
+            let (selector, seal) = seal.split_at(4);
+            if selector != verifier_selector() {
+                return Err(anyhow!(
+                    "seal selector {} is not this verifier's {}",
+                    hex::encode(selector),
+                    hex::encode(verifier_selector())
+                ));
+            }
+            seal
+        }
+        len => {
+            return Err(anyhow!(
+                "expected a {}-byte Groth16 seal, got {} bytes",
+                GROTH16_SEAL_LEN,
+                len
+            ))
+        }
+    };
+
+    let claim = ReceiptClaim::ok(
+        risc0_zkvm::sha::Digest::from_bytes(image_id),
+        journal_bytes.to_vec(),
+    );
+    let receipt = Receipt::new(
+        InnerReceipt::Groth16(Groth16Receipt::new(
+            seal.to_vec(),
+            claim.into(),
+            Groth16ReceiptVerifierParameters::default().digest(),
+        )),
+        journal_bytes.to_vec(),
+    );
+    verify_receipt(&receipt, image_id)
+}
+
+// ─── Journal decode ───────────────────────────────────────────────────────────
+
+pub fn decode_journal(bytes: &[u8]) -> Result<GameJournal> {
+    GameJournal::decode(bytes).map_err(|e| anyhow!("{}", e))
+}
+
+// ─── Receipt encoding ─────────────────────────────────────────────────────────
+
+/// Serializes a receipt as CBOR, the format GET /receipt/{job_id} serves.
+pub fn encode_receipt(receipt: &Receipt) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ciborium::into_writer(receipt, &mut out).map_err(|e| anyhow!("encoding receipt: {}", e))?;
+    Ok(out)
+}
+
+pub fn decode_receipt(bytes: &[u8]) -> Result<Receipt> {
+    ciborium::from_reader(bytes).map_err(|e| anyhow!("decoding receipt: {}", e))
+}
+
 // ─── Utilities ────────────────────────────────────────────────────────────────
 
 pub fn sha256(data: &[u8]) -> [u8; 32] {
@@ -446,6 +789,32 @@ pub fn digest_to_bytes(digest: risc0_zkvm::sha::Digest) -> [u8; 32] {
     out
 }
 
+/// The 4-byte selector the on-chain verifier router expects in front of a
+/// Groth16 seal: the start of the digest of the Groth16 verifier parameters
+/// (control root, BN254 control id, verifying key) of the risc0 version
+/// linked in. It changes when a risc0 upgrade changes those parameters, and
+/// the router must then have a verifier registered under the new selector.
+pub fn verifier_selector() -> [u8; 4] {
+    let digest = Groth16ReceiptVerifierParameters::default().digest();
+    let mut out = [0u8; 4];
+    out.copy_from_slice(&digest.as_bytes()[..4]);
+    out
+}
+
+/// The seal as the verifier and leaderboard contracts take it: the verifier
+/// selector followed by the Groth16 seal. Other receipt kinds can't be
+/// verified on-chain, so their bare seal is returned.
+pub fn encode_seal(receipt: &Receipt) -> Result<Vec<u8>> {
+    let seal = receipt_seal_bytes(receipt)?;
+    if !matches!(&receipt.inner, InnerReceipt::Groth16(_)) {
+        return Ok(seal);
+    }
+    let mut out = verifier_selector().to_vec();
+    out.extend_from_slice(&seal);
+    Ok(out)
+}
+
+/// The raw seal bytes, without a selector.
 pub fn receipt_seal_bytes(receipt: &Receipt) -> Result<Vec<u8>> {
     match &receipt.inner {
         InnerReceipt::Groth16(inner) => Ok(inner.seal.clone()),
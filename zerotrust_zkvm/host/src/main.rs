@@ -1,78 +1,81 @@
 use methods::GUEST_ZEROTRUST_ZKVM_ID;
 
-use actix_cors::Cors;
-use actix_web::{middleware::Logger, web, App, HttpResponse, HttpServer, Result};
-use dashmap::DashMap;
+use actix_web::{
+    middleware::{DefaultHeaders, Logger},
+    web, App, HttpRequest, HttpResponse, HttpServer, Result,
+};
 use hex::encode as hex_encode;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 use host::{
-    digest_to_bytes, fetch_bot_config_from_stellar, prove_game, ActionEntry, ProveInput,
+    digest_to_bytes, fetch_bot_config_from_stellar, journal_response, sha256, simulate_round,
+    verifier_selector, ActionEntry, BotConfigZK, JournalResponse, ProveInput, ProveProgress,
+    ReceiptKind,
 };
 
-// ─── Job state ────────────────────────────────────────────────────────────────
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(tag = "status")]
-enum JobState {
-    #[serde(rename = "pending")]
-    Pending,
-    #[serde(rename = "proving")]
-    Proving,
-    #[serde(rename = "done")]
-    Done { result: ProveResponse },
-    #[serde(rename = "failed")]
-    Failed { error: String },
-}
+mod auth;
+mod config;
+mod events;
+mod jobs;
+mod metrics;
+mod queue;
+mod ratelimit;
+mod verify;
+use auth::{RequestAuth, SignedRequest, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use config::HttpConfig;
+use events::{EventStream, JobEvent};
+use jobs::{JobState, JobStore, STATE_NAMES};
+use metrics::Metrics;
+use queue::{CancelOutcome, ProveQueue, SubmitError, PROVE_JOB_ARG};
+use ratelimit::{IpRateLimit, ProveRateLimits};
+use verify::{image_id_or_default, verify_proof, Proof, VerifyRequest, VERIFY_ARG};
+
+// ─── Job store config ─────────────────────────────────────────────────────────
+
+const DEFAULT_JOB_STORE_PATH: &str = "./jobs.db";
+const DEFAULT_JOB_RETENTION_SECS: u64 = 24 * 60 * 60;
+const JOB_REAP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+// ─── Proving queue config ─────────────────────────────────────────────────────
+
+const DEFAULT_PROVE_WORKERS: usize = 1; // one GPU
+const DEFAULT_PROVE_QUEUE_DEPTH: usize = 16;
+
+// ─── Request auth config ──────────────────────────────────────────────────────
+
+const DEFAULT_AUTH_MAX_SKEW_SECS: u64 = 5 * 60;
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
 
-type JobStore = Arc<DashMap<String, JobState>>;
+// ─── Rate limit config ────────────────────────────────────────────────────────
+
+const DEFAULT_PROVE_LIMIT_PER_PLAYER: usize = 10;
+const DEFAULT_PROVE_LIMIT_PER_IP: usize = 30;
+const DEFAULT_PROVE_LIMIT_WINDOW_SECS: u64 = 60 * 60;
+const DEFAULT_SIMULATE_LIMIT_PER_IP: usize = 60;
+const DEFAULT_SIMULATE_LIMIT_WINDOW_SECS: u64 = 60;
+const DEFAULT_VERIFY_LIMIT_PER_IP: usize = 30;
+const DEFAULT_VERIFY_LIMIT_WINDOW_SECS: u64 = 60;
 
 // ─── Request / Response types ─────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize, Clone)]
 struct ProveRequest {
-    challenge_id: String,
+    /// Nonce from the leaderboard contract's new_challenge
+    challenge_id: u32,
     player_pubkey: String,
     bot_config_id: u32,
     action_log: Vec<ActionEntry>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct ProveResponse {
-    success: bool,
-    proof_verified: bool,
-    execution_time_ms: u128,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    seal_hex: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    image_id_hex: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    journal_sha256_hex: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    journal_bytes_hex: Option<String>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    journal: Option<JournalResponse>,
-
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize)]
-struct JournalResponse {
-    challenge_id: String,
-    player_pubkey: String,
-    bot_config_id: u32,
-    threats_cured: u32,
-    systems_destroyed: u32,
-    data_leaked_x100: u32,
-    score: u32,
-    duration_ms: u32,
-    accuracy_bps: u32,
+#[derive(Debug, Serialize)]
+struct SimulateResponse {
+    journal_sha256_hex: String,
+    journal_bytes_hex: String,
+    journal: JournalResponse,
 }
 
 #[derive(Debug, Serialize)]
@@ -85,6 +88,9 @@ struct StatusResponse {
     job_id: String,
     #[serde(flatten)]
     state: JobState,
+    /// Latest milestone while the job is proving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<ProveProgress>,
 }
 
 #[derive(Debug, Serialize)]
@@ -92,10 +98,22 @@ struct HealthResponse {
     status: String,
     image_id_hex: String,
     version: String,
+    receipt_kind: String,
+    dev_mode: bool,
+    /// Expected in front of Groth16 seals by the on-chain verifier router.
+    verifier_selector_hex: String,
 }
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
+/// Parses an env var, falling back to `default` when unset or malformed.
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 fn image_id_hex() -> String {
     hex_encode(digest_to_bytes(GUEST_ZEROTRUST_ZKVM_ID.into()))
 }
@@ -110,6 +128,141 @@ fn parse_hex_32(s: &str) -> anyhow::Result<[u8; 32]> {
     Ok(out)
 }
 
+/// Checks the fields of a prove/simulate request that don't need the bot config.
+/// Returns the decoded challenge_id and player_pubkey, or why the request is bad.
+fn validate_prove_request(req: &ProveRequest) -> std::result::Result<(u32, [u8; 32]), String> {
+    // ── Validate player_pubkey ────────────────────────────────────────────────
+    let player_pubkey =
+        parse_hex_32(&req.player_pubkey).map_err(|e| format!("invalid player_pubkey: {}", e))?;
+
+    // ── Validate action log ───────────────────────────────────────────────────
+    if req.action_log.is_empty() {
+        return Err("action_log must not be empty".to_string());
+    }
+    for (i, entry) in req.action_log.iter().enumerate() {
+        if entry.unassigned_at_ms <= entry.assigned_at_ms {
+            return Err(format!(
+                "action_log[{}]: unassigned_at_ms ({}) must be > assigned_at_ms ({})",
+                i, entry.unassigned_at_ms, entry.assigned_at_ms
+            ));
+        }
+    }
+
+    Ok((req.challenge_id, player_pubkey))
+}
+
+/// The client's address: the TCP peer, or the first X-Forwarded-For hop when
+/// TRUST_FORWARDED_FOR is set (only behind a proxy that overwrites it).
+fn client_ip(http: &HttpRequest) -> Option<std::net::IpAddr> {
+    let info = http.connection_info();
+    let addr = if env_or("TRUST_FORWARDED_FOR", false) {
+        info.realip_remote_addr()
+    } else {
+        info.peer_addr()
+    }?;
+    // Forwarded addresses may carry a port
+    addr.parse()
+        .ok()
+        .or_else(|| addr.parse::<std::net::SocketAddr>().ok().map(|a| a.ip()))
+}
+
+/// Identifies a proof request by what determines the proof: the player, the
+/// challenge, the bot and the action log. Retries of a request hash the same.
+fn request_digest(
+    challenge_id: u32,
+    player_pubkey: &[u8; 32],
+    bot_config_id: u32,
+    action_log: &[ActionEntry],
+) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(44 + action_log.len() * 16);
+    bytes.extend_from_slice(player_pubkey);
+    bytes.extend_from_slice(&challenge_id.to_le_bytes());
+    bytes.extend_from_slice(&bot_config_id.to_le_bytes());
+    bytes.extend_from_slice(&(action_log.len() as u32).to_le_bytes());
+    for entry in action_log {
+        bytes.extend_from_slice(&entry.dev_index.to_le_bytes());
+        bytes.extend_from_slice(&entry.threat_index.to_le_bytes());
+        bytes.extend_from_slice(&entry.assigned_at_ms.to_le_bytes());
+        bytes.extend_from_slice(&entry.unassigned_at_ms.to_le_bytes());
+    }
+    sha256(&bytes)
+}
+
+/// What an earlier submission of the same request left behind.
+enum Duplicate {
+    /// A job still pending, proving or done — reused rather than proven again.
+    Job(String, JobState),
+    /// The Idempotency-Key was first used for a different request.
+    KeyReused,
+}
+
+/// Looks for the job an earlier identical submission created. Failed,
+/// cancelled and expired jobs don't count, so those requests can be retried.
+fn find_duplicate(
+    jobs: &JobStore,
+    digest: &[u8; 32],
+    idempotency_key: Option<&str>,
+) -> anyhow::Result<Option<Duplicate>> {
+    let reusable = |state: &JobState| {
+        matches!(
+            state,
+            JobState::Pending { .. } | JobState::Proving | JobState::Done { .. }
+        )
+    };
+
+    if let Some(key) = idempotency_key {
+        if let Some((key_digest, job_id, record)) = jobs.find_idempotency_key(key)? {
+            if key_digest != *digest {
+                return Ok(Some(Duplicate::KeyReused));
+            }
+            if reusable(&record.state) {
+                return Ok(Some(Duplicate::Job(job_id, record.state)));
+            }
+        }
+    }
+
+    Ok(jobs
+        .find_request(digest)?
+        .filter(|(_, record)| reusable(&record.state))
+        .map(|(job_id, record)| Duplicate::Job(job_id, record.state)))
+}
+
+/// Turns away a submission after its job was reserved: the job fails, so a
+/// duplicate that found it sees why, and the signature is given back so the
+/// request can be sent again without signing it anew.
+fn abandon(jobs: &JobStore, auth: &RequestAuth, signed: &SignedRequest, job_id: &str, error: &str) {
+    // Both at once, or a retry in between would find neither
+    let _submitting = jobs.lock_submissions();
+    auth.release(signed);
+    let state = JobState::Failed {
+        error: error.to_string(),
+    };
+    if let Err(e) = jobs.set_state(job_id, state) {
+        log::error!("Reserved job {} could not be failed: {}", job_id, e);
+    }
+}
+
+/// Fetches a bot config from Stellar, recording the RPC's latency and outcome.
+async fn fetch_bot_config(metrics: &Metrics, bot_config_id: u32) -> anyhow::Result<BotConfigZK> {
+    let start = Instant::now();
+    let result = fetch_bot_config_from_stellar(bot_config_id).await;
+    metrics.observe_stellar_rpc(start.elapsed(), result.is_ok());
+    result
+}
+
+/// A stored job state with the live queue position filled in if pending.
+fn live_state(queue: &ProveQueue, job_id: &str, state: JobState) -> JobState {
+    match (state, queue.position(job_id)) {
+        (JobState::Pending { .. }, Some(pos)) => JobState::Pending {
+            queue_position: Some(pos.position),
+            estimated_start_at: Some(jobs::unix_now() + pos.estimated_wait.as_secs()),
+        },
+        (state, _) => state,
+    }
+}
+
+// ─── Proving ──────────────────────────────────────────────────────────────────
+
 // ─── Endpoints ────────────────────────────────────────────────────────────────
 
 async fn health() -> Result<HttpResponse> {
@@ -117,6 +270,9 @@ async fn health() -> Result<HttpResponse> {
         status: "healthy".to_string(),
         image_id_hex: image_id_hex(),
         version: "1.0.0".to_string(),
+        receipt_kind: ReceiptKind::from_env().as_str().to_string(),
+        dev_mode: ReceiptKind::from_env().is_fake(),
+        verifier_selector_hex: hex_encode(verifier_selector()),
     }))
 }
 
@@ -130,160 +286,523 @@ async fn get_image_id() -> Result<HttpResponse> {
     }))
 }
 
+/// GET /metrics
+/// Prometheus text exposition.
+async fn metrics_endpoint(
+    metrics: web::Data<Metrics>,
+    jobs: web::Data<JobStore>,
+    queue: web::Data<ProveQueue>,
+) -> Result<HttpResponse> {
+    let (waiting, running) = queue.depth();
+    metrics.queue_waiting.set(waiting as i64);
+    metrics.queue_running.set(running as i64);
+
+    // Scans the job store, which the reaper keeps to about a day of jobs
+    let jobs = jobs.into_inner();
+    match web::block(move || jobs.count_by_state()).await {
+        Ok(Ok(counts)) => {
+            for state in STATE_NAMES {
+                let count = counts.get(state).copied().unwrap_or(0);
+                metrics.jobs.with_label_values(&[state]).set(count as i64);
+            }
+        }
+        Ok(Err(e)) => log::error!("Job counts unavailable: {}", e),
+        Err(e) => log::error!("Job counts unavailable: {}", e),
+    }
+
+    match metrics.encode() {
+        Ok(body) => Ok(HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(body)),
+        Err(e) => Ok(HttpResponse::InternalServerError().body(e.to_string())),
+    }
+}
+
 /// POST /prove
-/// Validates input, fetches bot config, spawns proving task, returns job_id immediately.
+/// Validates input and the player's signature, fetches bot config, queues the
+/// proving job, returns job_id immediately.
 async fn submit_prove(
     jobs: web::Data<JobStore>,
-    req: web::Json<ProveRequest>,
+    queue: web::Data<ProveQueue>,
+    metrics: web::Data<Metrics>,
+    auth: web::Data<RequestAuth>,
+    limits: web::Data<ProveRateLimits>,
+    http: HttpRequest,
+    body: web::Bytes,
 ) -> Result<HttpResponse> {
-    let req = req.into_inner();
-
-    // ── Validate player_pubkey ────────────────────────────────────────────────
-    let player_pubkey = match parse_hex_32(&req.player_pubkey) {
-        Ok(k) => k,
+    // Parsed by hand: the signature covers the raw body
+    let req: ProveRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
         Err(e) => {
+            metrics.reject("invalid_body");
             return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                "error": format!("invalid player_pubkey: {}", e)
-            })))
+                "error": format!("invalid request body: {}", e)
+            })));
+        }
+    };
+
+    let (challenge_id, player_pubkey) = match validate_prove_request(&req) {
+        Ok(v) => v,
+        Err(error) => {
+            metrics.reject("invalid_request");
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": error })));
         }
     };
 
-    // ── Validate challenge_id ─────────────────────────────────────────────────
-    let challenge_id_bytes = match parse_hex_32(&req.challenge_id) {
-        Ok(b) => b,
+    // ── Only the player's own key may request their proof ─────────────────────
+    let header = |name| http.headers().get(name).and_then(|v| v.to_str().ok());
+    let signed = match auth.verify(
+        &player_pubkey,
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &body,
+    ) {
+        Ok(signed) => signed,
         Err(e) => {
-            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                "error": format!("invalid challenge_id: {}", e)
-            })))
+            metrics.reject("unauthorized");
+            return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": e.to_string()
+            })));
         }
     };
-    let challenge_id = u32::from_le_bytes(challenge_id_bytes[..4].try_into().unwrap());
 
-    // ── Validate action log ───────────────────────────────────────────────────
-    if req.action_log.is_empty() {
-        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "action_log must not be empty"
-        })));
-    }
-    for (i, entry) in req.action_log.iter().enumerate() {
-        if entry.unassigned_at_ms <= entry.assigned_at_ms {
-            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+    // ── Duplicates get the job they already created ──────────────────────────
+    let digest = request_digest(
+        challenge_id,
+        &player_pubkey,
+        req.bot_config_id,
+        &req.action_log,
+    );
+    // Keys are scoped to the player so two players can't collide
+    let idempotency_key = match http.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        None => None,
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.is_empty() => Some(format!("{}:{}", hex_encode(player_pubkey), key)),
+            _ => {
+                metrics.reject("invalid_request");
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("invalid {} header", IDEMPOTENCY_KEY_HEADER)
+                })));
+            }
+        },
+    };
+    // Held until the job is reserved: of two identical requests in flight,
+    // one reserves the job and the other finds it
+    let job_id = Uuid::new_v4().to_string();
+    let submitting = jobs.lock_submissions();
+    match find_duplicate(&jobs, &digest, idempotency_key.as_deref()) {
+        Ok(None) => {}
+        Ok(Some(Duplicate::Job(job_id, state))) => {
+            log::info!("Duplicate submission for job {} ({})", job_id, state.name());
+            // A finished proof is returned straight away
+            let mut resp = match state {
+                JobState::Done { .. } => HttpResponse::Ok(),
+                _ => HttpResponse::Accepted(),
+            };
+            resp.insert_header(("Idempotent-Replayed", "true"));
+            return Ok(resp.json(StatusResponse {
+                job_id,
+                state,
+                progress: None,
+            }));
+        }
+        Ok(Some(Duplicate::KeyReused)) => {
+            metrics.reject("idempotency_key_reused");
+            return Ok(HttpResponse::UnprocessableEntity().json(serde_json::json!({
                 "error": format!(
-                    "action_log[{}]: unassigned_at_ms ({}) must be > assigned_at_ms ({})",
-                    i, entry.unassigned_at_ms, entry.assigned_at_ms
+                    "{} was already used for a different request",
+                    IDEMPOTENCY_KEY_HEADER
                 )
             })));
         }
+        Err(e) => {
+            metrics.reject("store_error");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to look up earlier submissions: {}", e)
+            })));
+        }
+    }
+
+    // ── Rate limits, checked before the signature is used up ──────────────────
+    if let Err(limited) = limits.acquire(&player_pubkey, client_ip(&http)) {
+        metrics.reject("rate_limited");
+        let retry_after = limited.retry_after.as_secs().max(1);
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(serde_json::json!({
+                "error": format!(
+                    "rate limit exceeded: at most {} proofs per {} per {}s",
+                    limited.limit,
+                    limited.scope,
+                    limits.window().as_secs()
+                ),
+                "retry_after_secs": retry_after,
+            })));
     }
 
+    // ── Not a retry, so the signature is used up ──────────────────────────────
+    if let Err(e) = auth.consume(&signed) {
+        metrics.reject("unauthorized");
+        return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": e.to_string()
+        })));
+    }
+
+    // ── Reserve the job before the slow checks ───────────────────────────────
+    if let Err(e) = jobs.reserve(&job_id, &digest, idempotency_key.as_deref()) {
+        metrics.reject("store_error");
+        return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("failed to store job: {}", e)
+        })));
+    }
+    drop(submitting);
+
     // ── Fetch bot config from Stellar (async, before spawning) ────────────────
-    let bot_config = match fetch_bot_config_from_stellar(req.bot_config_id).await {
+    let bot_config = match fetch_bot_config(&metrics, req.bot_config_id).await {
         Ok(cfg) => cfg,
         Err(e) => {
+            metrics.reject("stellar_rpc");
+            abandon(&jobs, &auth, &signed, &job_id, "failed to fetch bot config");
             return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": format!("failed to fetch bot config (token_id={}): {}", req.bot_config_id, e)
             })))
         }
     };
 
-    // ── Create job ────────────────────────────────────────────────────────────
-    let job_id = Uuid::new_v4().to_string();
-    jobs.insert(job_id.clone(), JobState::Pending);
+    let input = ProveInput {
+        challenge_id,
+        player_pubkey,
+        bot_config_id: req.bot_config_id,
+        bot_config,
+        action_log: req.action_log,
+    };
 
-    log::info!("Job {} created for bot_config_id={}", job_id, req.bot_config_id);
+    // ── Dry-run natively so a log the guest would reject never reaches the GPU ─
+    if let Err(e) = simulate_round(&input) {
+        metrics.reject("invalid_action_log");
+        abandon(&jobs, &auth, &signed, &job_id, &e.to_string());
+        return Ok(HttpResponse::UnprocessableEntity().json(serde_json::json!({
+            "error": e.to_string()
+        })));
+    }
 
-    // ── Spawn blocking prove task ─────────────────────────────────────────────
-    let jobs_clone = jobs.clone();
-    let job_id_clone = job_id.clone();
+    // ── Queue the reserved job ────────────────────────────────────────────────
+    let bot_config_id = input.bot_config_id;
+    // Writes to the job store and flushes, so off the async workers
+    let submitted = {
+        let (queue, job_id) = (queue.into_inner(), job_id.clone());
+        web::block(move || queue.submit(&job_id, input))
+            .await
+            .unwrap_or_else(|e| Err(SubmitError::Store(e.into())))
+    };
+    match submitted {
+        Ok(position) => {
+            log::info!(
+                "Job {} queued at position {} for bot_config_id={}",
+                job_id,
+                position,
+                bot_config_id
+            );
+        }
+        Err(SubmitError::QueueFull) => {
+            metrics.reject("queue_full");
+            abandon(&jobs, &auth, &signed, &job_id, "proving queue was full");
+            return Ok(HttpResponse::TooManyRequests().json(serde_json::json!({
+                "error": "proving queue is full, try again later"
+            })));
+        }
+        Err(SubmitError::Store(e)) => {
+            log::error!("Job {} could not be stored: {}", job_id, e);
+            metrics.reject("store_error");
+            abandon(&jobs, &auth, &signed, &job_id, "job could not be stored");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to store job: {}", e)
+            })));
+        }
+    }
 
-    actix_web::rt::spawn(async move {
-        jobs_clone.insert(job_id_clone.clone(), JobState::Proving);
+    // ── Return job_id immediately ─────────────────────────────────────────────
+    Ok(HttpResponse::Accepted().json(SubmitResponse { job_id }))
+}
 
-        let input = ProveInput {
-            challenge_id,
-            player_pubkey,
-            bot_config_id: req.bot_config_id,
-            bot_config,
-            action_log: req.action_log.clone(),
-        };
+/// POST /simulate
+/// Runs the round natively (no proof) and returns the journal the prover would commit.
+async fn simulate(
+    metrics: web::Data<Metrics>,
+    limit: web::Data<IpRateLimit>,
+    http: HttpRequest,
+    req: web::Json<ProveRequest>,
+) -> Result<HttpResponse> {
+    let req = req.into_inner();
 
-        let start = Instant::now();
-
-        let result = actix_web::web::block(move || prove_game(&input)).await;
-
-        match result {
-            Ok(Ok(prove_result)) => {
-                let elapsed = start.elapsed().as_millis();
-                let j = &prove_result.journal;
-
-                let response = ProveResponse {
-                    success: true,
-                    proof_verified: true,
-                    execution_time_ms: elapsed,
-                    seal_hex: Some(format!("0x{}", hex_encode(&prove_result.seal))),
-                    image_id_hex: Some(hex_encode(prove_result.image_id)),
-                    journal_sha256_hex: Some(hex_encode(prove_result.journal_sha256)),
-                    journal_bytes_hex: Some(format!(
-                        "0x{}",
-                        hex_encode(&prove_result.journal_bytes)
-                    )),
-                    journal: Some(JournalResponse {
-                        challenge_id: hex_encode(&prove_result.journal_bytes[0..4]),
-                        player_pubkey: hex_encode(j.player_pubkey),
-                        bot_config_id: j.bot_config_id,
-                        threats_cured: j.threats_cured,
-                        systems_destroyed: j.systems_destroyed,
-                        data_leaked_x100: j.data_leaked_x100,
-                        score: j.score,
-                        duration_ms: j.duration_ms,
-                        accuracy_bps: j.accuracy_bps,
-                    }),
-                    error: None,
-                };
-
-                log::info!("Job {} done in {}ms", job_id_clone, elapsed);
-                jobs_clone.insert(job_id_clone, JobState::Done { result: response });
-            }
-            Ok(Err(e)) => {
-                log::error!("Job {} failed: {}", job_id_clone, e);
-                jobs_clone.insert(
-                    job_id_clone,
-                    JobState::Failed {
-                        error: format!("proof generation failed: {}", e),
-                    },
-                );
-            }
-            Err(e) => {
-                log::error!("Job {} thread pool error: {}", job_id_clone, e);
-                jobs_clone.insert(
-                    job_id_clone,
-                    JobState::Failed {
-                        error: format!("thread pool error: {}", e),
-                    },
-                );
-            }
+    // Every dry run fetches the bot config from Stellar
+    if let Err(limited) = limit.acquire(client_ip(&http)) {
+        let retry_after = limited.retry_after.as_secs().max(1);
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(serde_json::json!({
+                "error": format!(
+                    "rate limit exceeded: at most {} simulations per {} per {}s",
+                    limited.limit,
+                    limited.scope,
+                    limit.window().as_secs()
+                ),
+                "retry_after_secs": retry_after,
+            })));
+    }
+
+    let (challenge_id, player_pubkey) = match validate_prove_request(&req) {
+        Ok(v) => v,
+        Err(error) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": error })))
         }
-    });
+    };
 
-    // ── Return job_id immediately ─────────────────────────────────────────────
-    Ok(HttpResponse::Accepted().json(SubmitResponse { job_id }))
+    let bot_config = match fetch_bot_config(&metrics, req.bot_config_id).await {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to fetch bot config (token_id={}): {}", req.bot_config_id, e)
+            })))
+        }
+    };
+
+    let input = ProveInput {
+        challenge_id,
+        player_pubkey,
+        bot_config_id: req.bot_config_id,
+        bot_config,
+        action_log: req.action_log,
+    };
+
+    match simulate_round(&input) {
+        Ok(journal) => {
+            let journal_bytes = journal.encode();
+            Ok(HttpResponse::Ok().json(SimulateResponse {
+                journal_sha256_hex: hex_encode(sha256(&journal_bytes)),
+                journal_bytes_hex: format!("0x{}", hex_encode(journal_bytes)),
+                journal: journal_response(&journal),
+            }))
+        }
+        Err(e) => Ok(HttpResponse::UnprocessableEntity().json(serde_json::json!({
+            "error": e.to_string()
+        }))),
+    }
+}
+
+/// POST /verify
+/// Verifies a receipt, or a Groth16 seal with its journal, without going
+/// on-chain, and decodes the round it proves. A body sent as
+/// `application/cbor` is taken to be a receipt from GET /receipt/{job_id},
+/// checked against this server's image id.
+async fn verify(
+    limit: web::Data<IpRateLimit>,
+    http: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse> {
+    // Unsigned, and each check can take a STARK verification's worth of CPU
+    if let Err(limited) = limit.acquire(client_ip(&http)) {
+        let retry_after = limited.retry_after.as_secs().max(1);
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(serde_json::json!({
+                "error": format!(
+                    "rate limit exceeded: at most {} verifications per {} per {}s",
+                    limited.limit,
+                    limited.scope,
+                    limit.window().as_secs()
+                ),
+                "retry_after_secs": retry_after,
+            })));
+    }
+
+    let is_cbor = http
+        .headers()
+        .get("Content-Type")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/cbor"));
+
+    let decoded = if is_cbor {
+        Proof::from_cbor(&body).and_then(|proof| Ok((proof, image_id_or_default(None)?)))
+    } else {
+        serde_json::from_slice::<VerifyRequest>(&body)
+            .map_err(anyhow::Error::from)
+            .and_then(|req| req.decode())
+    };
+    let (proof, image_id) = match decoded {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("{:#}", e)
+            })))
+        }
+    };
+
+    let response = web::block(move || verify_proof(&proof, image_id)).await?;
+    if response.verified {
+        Ok(HttpResponse::Ok().json(response))
+    } else {
+        Ok(HttpResponse::UnprocessableEntity().json(response))
+    }
 }
 
 /// GET /status/{job_id}
 async fn job_status(
     jobs: web::Data<JobStore>,
+    queue: web::Data<ProveQueue>,
     path: web::Path<String>,
 ) -> Result<HttpResponse> {
     let job_id = path.into_inner();
 
     match jobs.get(&job_id) {
-        Some(state) => Ok(HttpResponse::Ok().json(StatusResponse {
-            job_id,
-            state: state.clone(),
-        })),
-        None => Ok(HttpResponse::NotFound().json(serde_json::json!({
+        Ok(Some(record)) => {
+            // Queue position and progress are live, not stored
+            let state = live_state(&queue, &job_id, record.state);
+            let progress = queue.progress(&job_id);
+            Ok(HttpResponse::Ok().json(StatusResponse {
+                job_id,
+                state,
+                progress,
+            }))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
             "error": format!("job {} not found", job_id)
         }))),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("failed to read job {}: {}", job_id, e)
+        }))),
+    }
+}
+
+/// GET /status/{job_id}/events
+/// Server-sent events: the current state, then every state transition and
+/// progress milestone until the job finishes.
+async fn job_events(
+    jobs: web::Data<JobStore>,
+    queue: web::Data<ProveQueue>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+
+    // Subscribe before reading the state so no transition falls in between
+    let events = queue.subscribe();
+    let record = match jobs.get(&job_id) {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": format!("job {} not found", job_id)
+            })))
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to read job {}: {}", job_id, e)
+            })))
+        }
+    };
+
+    let mut initial = vec![JobEvent::state(
+        &job_id,
+        live_state(&queue, &job_id, record.state),
+    )];
+    if let Some(progress) = queue.progress(&job_id) {
+        initial.push(JobEvent::progress(&job_id, progress));
+    }
+
+    let stream = EventStream::new(job_id, events, jobs.get_ref().clone(), initial);
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream.into_stream()))
+}
+
+/// DELETE /status/{job_id}
+/// Cancels a pending job or aborts a running one.
+async fn cancel_job(
+    jobs: web::Data<JobStore>,
+    queue: web::Data<ProveQueue>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+
+    // Records the new state, which flushes the job store
+    let cancelled = {
+        let (queue, job_id) = (queue.into_inner(), job_id.clone());
+        web::block(move || queue.cancel(&job_id)).await?
+    };
+    match cancelled {
+        CancelOutcome::Dequeued | CancelOutcome::Aborted => {
+            log::info!("Job {} cancelled", job_id);
+            Ok(HttpResponse::Ok().json(StatusResponse {
+                job_id,
+                state: JobState::Cancelled,
+                progress: None,
+            }))
+        }
+        CancelOutcome::NotActive => match jobs.get(&job_id) {
+            Ok(Some(record)) => Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("job {} has already finished", job_id),
+                "job_id": job_id,
+                "state": record.state,
+            }))),
+            Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": format!("job {} not found", job_id)
+            }))),
+            Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to read job {}: {}", job_id, e)
+            }))),
+        },
+    }
+}
+
+/// GET /receipt/{job_id}
+/// The full risc0 Receipt of a done job, CBOR-encoded, for verifying offline
+/// against the image id or archiving.
+async fn get_receipt(jobs: web::Data<JobStore>, path: web::Path<String>) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+
+    let record = match jobs.get(&job_id) {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": format!("job {} not found", job_id)
+            })))
+        }
+        Err(e) => {
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("failed to read job {}: {}", job_id, e)
+            })))
+        }
+    };
+    match record.state {
+        JobState::Done { .. } => {}
+        JobState::Expired => {
+            return Ok(HttpResponse::Gone().json(serde_json::json!({
+                "error": format!("job {} has expired and its receipt was dropped", job_id)
+            })))
+        }
+        state => {
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("job {} has no receipt", job_id),
+                "job_id": job_id,
+                "state": state,
+            })))
+        }
+    }
+
+    match jobs.receipt(&job_id) {
+        Ok(Some(receipt)) => Ok(HttpResponse::Ok()
+            .content_type("application/cbor")
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}.receipt.cbor\"", job_id),
+            ))
+            .body(receipt)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("receipt for job {} was not stored", job_id)
+        }))),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("failed to read receipt for job {}: {}", job_id, e)
+        }))),
     }
 }
 
@@ -294,28 +813,347 @@ async fn main() -> std::io::Result<()> {
     dotenv::dotenv().ok();
     env_logger::init();
 
+    // Child process spawned by the proving queue for a single job
+    if std::env::args().nth(1).as_deref() == Some(PROVE_JOB_ARG) {
+        return queue::run_prove_job();
+    }
+    if std::env::args().nth(1).as_deref() == Some(VERIFY_ARG) {
+        return verify::run_verify_cli(std::env::args().skip(2));
+    }
+
     println!("Zero Trust — RISC Zero Host");
     println!("Image ID: {}", image_id_hex());
+    println!("Verifier selector: {}", hex_encode(verifier_selector()));
+
+    let receipt_kind = ReceiptKind::from_env();
+    println!("Receipt kind: {}", receipt_kind.as_str());
+    if receipt_kind.is_fake() {
+        log::warn!(
+            "DEV MODE: ZERO_TRUST_RECEIPT_KIND=fake — receipts are not proofs, do not use in production"
+        );
+    }
 
-    let jobs: JobStore = Arc::new(DashMap::new());
+    // ── Request signatures ───────────────────────────────────────────────────
+    let auth = web::Data::new(RequestAuth::new(
+        env_or("PROVE_AUTH_REQUIRED", true),
+        env_or("PROVE_AUTH_MAX_SKEW_SECS", DEFAULT_AUTH_MAX_SKEW_SECS),
+    ));
+    if !auth.required() {
+        log::warn!("PROVE_AUTH_REQUIRED=false — anyone can request proofs for any player");
+    }
 
-    HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header()
-            .max_age(3600);
+    // ── Rate limits ──────────────────────────────────────────────────────────
+    let limits = web::Data::new(ProveRateLimits::new(
+        env_or("PROVE_LIMIT_PER_PLAYER", DEFAULT_PROVE_LIMIT_PER_PLAYER),
+        env_or("PROVE_LIMIT_PER_IP", DEFAULT_PROVE_LIMIT_PER_IP),
+        Duration::from_secs(env_or(
+            "PROVE_LIMIT_WINDOW_SECS",
+            DEFAULT_PROVE_LIMIT_WINDOW_SECS,
+        )),
+    ));
+    let simulate_limit = web::Data::new(IpRateLimit::new(
+        env_or("SIMULATE_LIMIT_PER_IP", DEFAULT_SIMULATE_LIMIT_PER_IP),
+        Duration::from_secs(env_or(
+            "SIMULATE_LIMIT_WINDOW_SECS",
+            DEFAULT_SIMULATE_LIMIT_WINDOW_SECS,
+        )),
+    ));
+    let verify_limit = web::Data::new(IpRateLimit::new(
+        env_or("VERIFY_LIMIT_PER_IP", DEFAULT_VERIFY_LIMIT_PER_IP),
+        Duration::from_secs(env_or(
+            "VERIFY_LIMIT_WINDOW_SECS",
+            DEFAULT_VERIFY_LIMIT_WINDOW_SECS,
+        )),
+    ));
+
+    // ── HTTP server ──────────────────────────────────────────────────────────
+    let http = HttpConfig::from_env().map_err(std::io::Error::other)?;
+    let tls = http.rustls_config().map_err(std::io::Error::other)?;
+    let (bind_addr, port, http_workers) = (http.bind_addr.clone(), http.port, http.workers);
+    match &http.cors_origins {
+        Some(origins) => println!("Listening on {} (CORS: {})", http.url(), origins.join(", ")),
+        None => println!("Listening on {} (CORS: any origin)", http.url()),
+    }
+
+    // ── Metrics ──────────────────────────────────────────────────────────────
+    let metrics = Arc::new(Metrics::new().map_err(std::io::Error::other)?);
+
+    // ── Job store — survives restarts ────────────────────────────────────────
+    let store_path =
+        std::env::var("JOB_STORE_PATH").unwrap_or_else(|_| DEFAULT_JOB_STORE_PATH.to_string());
+    let retention_secs = env_or("JOB_RETENTION_SECS", DEFAULT_JOB_RETENTION_SECS);
+
+    let jobs = JobStore::open(&store_path).map_err(std::io::Error::other)?;
+    println!("Job store: {} (finished jobs kept {}s)", store_path, retention_secs);
+
+    // ── Proving queue ─────────────────────────────────────────────────────────
+    let workers = env_or("PROVE_WORKERS", DEFAULT_PROVE_WORKERS);
+    let queue_depth = env_or("PROVE_QUEUE_DEPTH", DEFAULT_PROVE_QUEUE_DEPTH);
+    let queue = ProveQueue::new(jobs.clone(), metrics.clone(), workers, queue_depth);
+    println!("Proving queue: {} worker(s), depth {}", workers, queue_depth);
+
+    // Jobs cut off by the last shutdown start again from scratch
+    let requeued = jobs.requeue_unfinished().map_err(std::io::Error::other)?;
+    for (job_id, input) in requeued {
+        log::info!("Job {} re-queued after restart", job_id);
+        queue.requeue(job_id, input);
+    }
+    queue.start();
+
+    // Finished jobs expire after the retention period, then disappear
+    let reaper_jobs = jobs.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = tokio::time::interval(JOB_REAP_INTERVAL);
+        loop {
+            interval.tick().await;
+            // Walks the whole store and flushes, so off the async workers
+            let jobs = reaper_jobs.clone();
+            match web::block(move || jobs.reap(retention_secs)).await {
+                Ok(Ok((0, 0))) => {}
+                Ok(Ok((expired, removed))) => {
+                    log::info!("Expired {} job(s), removed {}", expired, removed)
+                }
+                Ok(Err(e)) => log::error!("Job reaper failed: {}", e),
+                Err(e) => log::error!("Job reaper failed: {}", e),
+            }
+        }
+    });
+
+    let server = HttpServer::new(move || {
+        // Every response in dev mode says so, whichever endpoint served it
+        let dev_mode_header = if receipt_kind.is_fake() {
+            DefaultHeaders::new().add(("X-Zero-Trust-Dev-Mode", "fake-receipts"))
+        } else {
+            DefaultHeaders::new()
+        };
 
         App::new()
             .wrap(Logger::default())
-            .wrap(cors)
+            .wrap(http.cors())
+            .wrap(dev_mode_header)
+            .app_data(web::PayloadConfig::new(http.max_body_bytes))
+            .app_data(web::JsonConfig::default().limit(http.max_body_bytes))
             .app_data(web::Data::new(jobs.clone()))
+            .app_data(web::Data::from(queue.clone()))
+            .app_data(web::Data::from(metrics.clone()))
+            .app_data(auth.clone())
+            .app_data(limits.clone())
             .route("/health", web::get().to(health))
             .route("/image-id", web::get().to(get_image_id))
+            .route("/metrics", web::get().to(metrics_endpoint))
             .route("/prove", web::post().to(submit_prove))
+            .route("/receipt/{job_id}", web::get().to(get_receipt))
+            // Each has its own per-IP limit; /verify takes whole receipts
+            .service(
+                web::resource("/simulate")
+                    .app_data(simulate_limit.clone())
+                    .route(web::post().to(simulate)),
+            )
+            .service(
+                web::resource("/verify")
+                    .app_data(verify_limit.clone())
+                    .app_data(web::PayloadConfig::new(http.max_verify_body_bytes))
+                    .route(web::post().to(verify)),
+            )
             .route("/status/{job_id}", web::get().to(job_status))
-    })
-    .bind("0.0.0.0:8080")?
-    .run()
-    .await
-}
\ No newline at end of file
+            .route("/status/{job_id}", web::delete().to(cancel_job))
+            .route("/status/{job_id}/events", web::get().to(job_events))
+    });
+    let server = match http_workers {
+        Some(workers) => server.workers(workers),
+        None => server,
+    };
+    let server = match tls {
+        Some(tls) => server.bind_rustls_0_23((bind_addr, port), tls)?,
+        None => server.bind((bind_addr, port))?,
+    };
+    server.run().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test};
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const SIGNED_MESSAGE_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+    /// Signs `body` the way the client does: SEP-53 over `"{timestamp}.{body}"`.
+    fn sign(key: &SigningKey, timestamp: &str, body: &[u8]) -> String {
+        let message = [SIGNED_MESSAGE_PREFIX, timestamp.as_bytes(), b".", body].concat();
+        STANDARD.encode(key.sign(&sha256(&message)).to_bytes())
+    }
+
+    /// A /prove request for one assignment, signed by its player.
+    struct SignedProve {
+        player_pubkey: [u8; 32],
+        action_log: Vec<ActionEntry>,
+        body: Vec<u8>,
+        timestamp: String,
+        signature: String,
+    }
+
+    impl SignedProve {
+        fn new(key: &SigningKey) -> Self {
+            let player_pubkey = key.verifying_key().to_bytes();
+            let action_log = vec![ActionEntry {
+                dev_index: 0,
+                threat_index: 0,
+                assigned_at_ms: 1_000,
+                unassigned_at_ms: 5_000,
+            }];
+            let body = serde_json::to_vec(&serde_json::json!({
+                "challenge_id": 11,
+                "player_pubkey": hex_encode(player_pubkey),
+                "bot_config_id": 3,
+                "action_log": action_log,
+            }))
+            .unwrap();
+            let timestamp = jobs::unix_now().to_string();
+            let signature = sign(key, &timestamp, &body);
+            SignedProve {
+                player_pubkey,
+                action_log,
+                body,
+                timestamp,
+                signature,
+            }
+        }
+
+        fn digest(&self) -> [u8; 32] {
+            request_digest(11, &self.player_pubkey, 3, &self.action_log)
+        }
+
+        fn post(&self) -> test::TestRequest {
+            test::TestRequest::post()
+                .uri("/prove")
+                .insert_header((TIMESTAMP_HEADER, self.timestamp.as_str()))
+                .insert_header((SIGNATURE_HEADER, self.signature.as_str()))
+                .set_payload(self.body.clone())
+        }
+    }
+
+    #[actix_web::test]
+    async fn verbatim_retry_gets_the_existing_job() {
+        let path = std::env::temp_dir().join(format!("jobs-{}", Uuid::new_v4()));
+        let jobs = JobStore::open(path.to_str().unwrap()).unwrap();
+        let metrics = Arc::new(Metrics::new().unwrap());
+        let queue = ProveQueue::new(jobs.clone(), metrics.clone(), 1, 16);
+        let auth = web::Data::new(RequestAuth::new(true, 300));
+        let limits = web::Data::new(ProveRateLimits::new(10, 30, Duration::from_secs(3600)));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(jobs.clone()))
+                .app_data(web::Data::from(queue))
+                .app_data(web::Data::from(metrics))
+                .app_data(auth.clone())
+                .app_data(limits)
+                .route("/prove", web::post().to(submit_prove)),
+        )
+        .await;
+
+        let request = SignedProve::new(&SigningKey::from_bytes(&[7; 32]));
+
+        // The first submission verified and used up the signature, then
+        // created the job; its response never reached the client.
+        let signed = auth
+            .verify(
+                &request.player_pubkey,
+                Some(&request.timestamp),
+                Some(&request.signature),
+                &request.body,
+            )
+            .unwrap();
+        auth.consume(&signed).unwrap();
+        let input = ProveInput {
+            challenge_id: 11,
+            player_pubkey: request.player_pubkey,
+            bot_config_id: 3,
+            bot_config: BotConfigZK {
+                bot_type: "malware".into(),
+                primary_target: "compute".into(),
+                secondary_targets: vec![],
+                damage_multiplier: 100,
+                threat_count: 3,
+                spawn_pattern: "steady".into(),
+                skill_diversity: "low".into(),
+                victory_condition: "time-survival".into(),
+                abilities: vec![],
+                resource_attack: "none".into(),
+            },
+            action_log: request.action_log.clone(),
+        };
+        jobs.reserve("job-1", &request.digest(), None).unwrap();
+        jobs.create("job-1", &input).unwrap();
+
+        for _ in 0..2 {
+            let resp = test::call_service(&app, request.post().to_request()).await;
+            assert_eq!(resp.status(), StatusCode::ACCEPTED);
+            assert_eq!(resp.headers().get("Idempotent-Replayed").unwrap(), "true");
+            let status: serde_json::Value = test::read_body_json(resp).await;
+            assert_eq!(status["job_id"], "job-1");
+        }
+
+        // A used signature still can't create a second job
+        jobs.set_state(
+            "job-1",
+            JobState::Failed {
+                error: "boom".to_string(),
+            },
+        )
+        .unwrap();
+        let resp = test::call_service(&app, request.post().to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    #[actix_web::test]
+    async fn duplicate_of_a_request_in_flight_gets_its_job() {
+        let path = std::env::temp_dir().join(format!("jobs-{}", Uuid::new_v4()));
+        let jobs = JobStore::open(path.to_str().unwrap()).unwrap();
+        let metrics = Arc::new(Metrics::new().unwrap());
+        let queue = ProveQueue::new(jobs.clone(), metrics.clone(), 1, 16);
+        let auth = web::Data::new(RequestAuth::new(true, 300));
+        let limits = web::Data::new(ProveRateLimits::new(10, 30, Duration::from_secs(3600)));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(jobs.clone()))
+                .app_data(web::Data::from(queue))
+                .app_data(web::Data::from(metrics))
+                .app_data(auth.clone())
+                .app_data(limits)
+                .route("/prove", web::post().to(submit_prove)),
+        )
+        .await;
+
+        let request = SignedProve::new(&SigningKey::from_bytes(&[9; 32]));
+
+        // The first submission got as far as its reservation and is now
+        // fetching the bot config.
+        {
+            let _submitting = jobs.lock_submissions();
+            let signed = auth
+                .verify(
+                    &request.player_pubkey,
+                    Some(&request.timestamp),
+                    Some(&request.signature),
+                    &request.body,
+                )
+                .unwrap();
+            auth.consume(&signed).unwrap();
+            jobs.reserve("job-1", &request.digest(), None).unwrap();
+        }
+
+        // The same request again, before the first has answered
+        let resp = test::call_service(&app, request.post().to_request()).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        assert_eq!(resp.headers().get("Idempotent-Replayed").unwrap(), "true");
+        let status: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(status["job_id"], "job-1");
+        assert_eq!(status["status"], "pending");
+
+        let _ = std::fs::remove_dir_all(path);
+    }
+}
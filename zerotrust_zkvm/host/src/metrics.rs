@@ -0,0 +1,143 @@
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::time::Duration;
+
+use host::GuestCycles;
+
+// Proofs take minutes on a GPU, seconds with fake receipts.
+const PROOF_DURATION_BUCKETS: &[f64] = &[
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 240.0, 360.0, 480.0, 600.0, 900.0, 1200.0, 1800.0, 3600.0,
+];
+const RPC_DURATION_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+// ─── Prometheus metrics ───────────────────────────────────────────────────────
+
+/// Everything /metrics exposes. Gauges that mirror current state (queue depth,
+/// jobs by state) are refreshed when scraped; the rest are recorded as it
+/// happens.
+pub struct Metrics {
+    registry: Registry,
+    pub queue_waiting: IntGauge,
+    pub queue_running: IntGauge,
+    pub jobs: IntGaugeVec,
+    proof_duration: HistogramVec,
+    stellar_rpc_duration: HistogramVec,
+    guest_user_cycles: HistogramVec,
+    guest_total_cycles: HistogramVec,
+    guest_segments: HistogramVec,
+    rejected: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("zerotrust".to_string()), None)?;
+
+        let cycle_buckets = prometheus::exponential_buckets(65_536.0, 2.0, 14)?;
+        let metrics = Metrics {
+            queue_waiting: IntGauge::new("queue_waiting", "Jobs waiting for a prove worker")?,
+            queue_running: IntGauge::new("queue_running", "Jobs being proven")?,
+            jobs: IntGaugeVec::new(Opts::new("jobs", "Stored jobs by state"), &["state"])?,
+            proof_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "proof_duration_seconds",
+                    "Time from a worker picking a job up to its proof finishing",
+                )
+                .buckets(PROOF_DURATION_BUCKETS.to_vec()),
+                &["receipt_kind", "outcome"],
+            )?,
+            stellar_rpc_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "stellar_rpc_duration_seconds",
+                    "Latency of bot config fetches from Stellar RPC",
+                )
+                .buckets(RPC_DURATION_BUCKETS.to_vec()),
+                &["outcome"],
+            )?,
+            guest_user_cycles: HistogramVec::new(
+                HistogramOpts::new(
+                    "guest_user_cycles",
+                    "Cycles executed by the guest per proof",
+                )
+                .buckets(cycle_buckets.clone()),
+                &["receipt_kind"],
+            )?,
+            guest_total_cycles: HistogramVec::new(
+                HistogramOpts::new(
+                    "guest_total_cycles",
+                    "Padded cycles the prover pays for per proof",
+                )
+                .buckets(cycle_buckets),
+                &["receipt_kind"],
+            )?,
+            guest_segments: HistogramVec::new(
+                HistogramOpts::new("guest_segments", "Segments per proof")
+                    .buckets(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]),
+                &["receipt_kind"],
+            )?,
+            rejected: IntCounterVec::new(
+                Opts::new("prove_rejected_total", "POST /prove requests turned away"),
+                &["reason"],
+            )?,
+            registry,
+        };
+
+        let collectors: [Box<dyn Collector>; 9] = [
+            Box::new(metrics.queue_waiting.clone()),
+            Box::new(metrics.queue_running.clone()),
+            Box::new(metrics.jobs.clone()),
+            Box::new(metrics.proof_duration.clone()),
+            Box::new(metrics.stellar_rpc_duration.clone()),
+            Box::new(metrics.guest_user_cycles.clone()),
+            Box::new(metrics.guest_total_cycles.clone()),
+            Box::new(metrics.guest_segments.clone()),
+            Box::new(metrics.rejected.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector)?;
+        }
+        Ok(metrics)
+    }
+
+    /// outcome is "done", "failed" or "cancelled".
+    pub fn observe_proof(&self, receipt_kind: &str, outcome: &str, took: Duration) {
+        self.proof_duration
+            .with_label_values(&[receipt_kind, outcome])
+            .observe(took.as_secs_f64());
+    }
+
+    /// Failures are counted by the histogram's `outcome="error"` series.
+    pub fn observe_stellar_rpc(&self, took: Duration, ok: bool) {
+        let outcome = if ok { "ok" } else { "error" };
+        self.stellar_rpc_duration
+            .with_label_values(&[outcome])
+            .observe(took.as_secs_f64());
+    }
+
+    /// Records what a finished proof's guest run cost.
+    pub fn observe_cycles(&self, receipt_kind: &str, cycles: &GuestCycles) {
+        let labels = [receipt_kind];
+        self.guest_user_cycles
+            .with_label_values(&labels)
+            .observe(cycles.user_cycles as f64);
+        self.guest_total_cycles
+            .with_label_values(&labels)
+            .observe(cycles.total_cycles as f64);
+        self.guest_segments
+            .with_label_values(&labels)
+            .observe(cycles.segments as f64);
+    }
+
+    pub fn reject(&self, reason: &str) {
+        self.rejected.with_label_values(&[reason]).inc();
+    }
+
+    /// The Prometheus text exposition of every metric.
+    pub fn encode(&self) -> prometheus::Result<String> {
+        let mut out = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut out)?;
+        Ok(String::from_utf8_lossy(&out).into_owned())
+    }
+}
@@ -0,0 +1,427 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{broadcast, Notify};
+
+use host::{
+    prove_game_with_progress, prove_response, ProveInput, ProveProgress, ProveResult, ReceiptKind,
+};
+
+use crate::events::{JobEvent, EVENT_BUFFER};
+use crate::jobs::{JobState, JobStore};
+use crate::metrics::Metrics;
+
+// Assumed proving time until a job has finished on this machine.
+const INITIAL_PROVE_ESTIMATE: Duration = Duration::from_secs(9 * 60);
+
+/// Argument that makes the host binary prove a single job from stdin instead
+/// of serving HTTP. Each proof runs in such a child process so it can be killed.
+pub const PROVE_JOB_ARG: &str = "--prove-job";
+
+// ─── Proving queue ────────────────────────────────────────────────────────────
+
+/// FIFO of jobs waiting to be proven, drained by a fixed number of workers so
+/// concurrent submissions don't fight over the GPU.
+pub struct ProveQueue {
+    jobs: JobStore,
+    metrics: Arc<Metrics>,
+    workers: usize,
+    max_depth: usize,
+    inner: Mutex<QueueInner>,
+    wake: Notify,
+    events: broadcast::Sender<JobEvent>,
+}
+
+struct QueueInner {
+    waiting: VecDeque<(String, ProveInput)>,
+    running: Vec<RunningJob>,
+    /// Running average of how long a proof takes, for start estimates.
+    avg_prove: Duration,
+}
+
+struct RunningJob {
+    job_id: String,
+    started: Instant,
+    cancel: Arc<Notify>,
+    cancelled: bool,
+    /// Latest milestone reported by its prover process.
+    progress: Option<ProveProgress>,
+}
+
+pub enum SubmitError {
+    QueueFull,
+    Store(anyhow::Error),
+}
+
+pub enum CancelOutcome {
+    /// Taken out of the queue before it started.
+    Dequeued,
+    /// Its prover process is being killed.
+    Aborted,
+    /// Not waiting or running — already finished, or unknown.
+    NotActive,
+}
+
+/// Where a pending job stands.
+pub struct QueuePosition {
+    /// 1 = next to start.
+    pub position: usize,
+    pub estimated_wait: Duration,
+}
+
+impl ProveQueue {
+    pub fn new(
+        jobs: JobStore,
+        metrics: Arc<Metrics>,
+        workers: usize,
+        max_depth: usize,
+    ) -> Arc<Self> {
+        Arc::new(ProveQueue {
+            jobs,
+            metrics,
+            workers: workers.max(1),
+            max_depth,
+            inner: Mutex::new(QueueInner {
+                waiting: VecDeque::new(),
+                running: Vec::new(),
+                avg_prove: INITIAL_PROVE_ESTIMATE,
+            }),
+            wake: Notify::new(),
+            events: broadcast::channel(EVENT_BUFFER).0,
+        })
+    }
+
+    /// Spawns the workers. Call once, from inside the actix runtime.
+    pub fn start(self: &Arc<Self>) {
+        for _ in 0..self.workers {
+            let queue = self.clone();
+            actix_web::rt::spawn(async move { queue.work().await });
+        }
+    }
+
+    /// Stores a reserved job's input and queues it, unless the queue is
+    /// already full. Returns its queue position. Blocks on the job store's
+    /// flush.
+    pub fn submit(&self, job_id: &str, input: ProveInput) -> Result<usize, SubmitError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.waiting.len() >= self.max_depth {
+            return Err(SubmitError::QueueFull);
+        }
+        self.jobs
+            .create(job_id, &input)
+            .map_err(SubmitError::Store)?;
+        inner.waiting.push_back((job_id.to_string(), input));
+        let position = inner.waiting.len();
+        drop(inner);
+
+        self.wake.notify_one();
+        self.flush();
+        Ok(position)
+    }
+
+    /// Queues a job that is already stored. Used for jobs recovered after a
+    /// restart, which are never turned away, even past `max_depth`; new
+    /// submissions are refused until the queue is back under it.
+    pub fn requeue(&self, job_id: String, input: ProveInput) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.waiting.push_back((job_id, input));
+        if inner.waiting.len() == self.max_depth + 1 {
+            log::warn!(
+                "Re-queued jobs exceed the queue depth of {}; new jobs are refused until it drains",
+                self.max_depth
+            );
+        }
+        drop(inner);
+        self.wake.notify_one();
+    }
+
+    /// How many jobs are waiting and how many are being proven.
+    pub fn depth(&self) -> (usize, usize) {
+        let inner = self.inner.lock().unwrap();
+        (inner.waiting.len(), inner.running.len())
+    }
+
+    pub fn position(&self, job_id: &str) -> Option<QueuePosition> {
+        let inner = self.inner.lock().unwrap();
+        let ahead = inner.waiting.iter().position(|(id, _)| id == job_id)?;
+
+        // Time until each worker frees up, then hand the jobs ahead out in order
+        let mut free_in: Vec<Duration> = inner
+            .running
+            .iter()
+            .map(|job| inner.avg_prove.saturating_sub(job.started.elapsed()))
+            .collect();
+        free_in.resize(self.workers.max(free_in.len()), Duration::ZERO);
+        for _ in 0..ahead {
+            if let Some(slot) = free_in.iter_mut().min() {
+                *slot += inner.avg_prove;
+            }
+        }
+
+        Some(QueuePosition {
+            position: ahead + 1,
+            estimated_wait: free_in.into_iter().min().unwrap_or_default(),
+        })
+    }
+
+    /// Latest progress of a running job.
+    pub fn progress(&self, job_id: &str) -> Option<ProveProgress> {
+        let inner = self.inner.lock().unwrap();
+        let job = inner.running.iter().find(|j| j.job_id == job_id)?;
+        job.progress.clone()
+    }
+
+    /// Every job's state transitions and progress, as they happen.
+    pub fn subscribe(&self) -> broadcast::Receiver<JobEvent> {
+        self.events.subscribe()
+    }
+
+    /// Cancels a waiting job, or aborts a running one by killing its prover
+    /// process. The job is recorded as Cancelled either way. Blocks on the job
+    /// store's flush.
+    pub fn cancel(&self, job_id: &str) -> CancelOutcome {
+        let mut inner = self.inner.lock().unwrap();
+        let outcome = if let Some(i) = inner.waiting.iter().position(|(id, _)| id == job_id) {
+            inner.waiting.remove(i);
+            CancelOutcome::Dequeued
+        } else if let Some(job) = inner.running.iter_mut().find(|j| j.job_id == job_id) {
+            job.cancelled = true;
+            job.cancel.notify_one();
+            CancelOutcome::Aborted
+        } else {
+            return CancelOutcome::NotActive;
+        };
+        // Recorded under the lock so a worker finishing now can't overwrite it
+        self.record_state(job_id, JobState::Cancelled);
+        drop(inner);
+
+        self.flush();
+        outcome
+    }
+
+    async fn next(self: &Arc<Self>) -> (String, ProveInput, Arc<Notify>) {
+        loop {
+            let started = {
+                let mut inner = self.inner.lock().unwrap();
+                inner.waiting.pop_front().map(|(job_id, input)| {
+                    let cancel = Arc::new(Notify::new());
+                    inner.running.push(RunningJob {
+                        job_id: job_id.clone(),
+                        started: Instant::now(),
+                        cancel: cancel.clone(),
+                        cancelled: false,
+                        progress: None,
+                    });
+                    self.record_state(&job_id, JobState::Proving);
+                    (job_id, input, cancel)
+                })
+            };
+            if let Some(started) = started {
+                let queue = self.clone();
+                if let Err(e) = tokio::task::spawn_blocking(move || queue.flush()).await {
+                    log::error!("Job store could not be flushed: {}", e);
+                }
+                return started;
+            }
+            self.wake.notified().await;
+        }
+    }
+
+    /// Takes a job off the running list. Returns false if it was cancelled,
+    /// in which case its state has already been recorded.
+    fn finish(&self, job_id: &str, took: Duration) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(i) = inner.running.iter().position(|j| j.job_id == job_id) else {
+            return false;
+        };
+        if inner.running.remove(i).cancelled {
+            return false;
+        }
+        inner.avg_prove = (inner.avg_prove * 3 + took) / 4;
+        true
+    }
+
+    async fn work(self: Arc<Self>) {
+        loop {
+            let (job_id, input, cancel) = self.next().await;
+
+            // The prover process reads the same setting
+            let receipt_kind = ReceiptKind::from_env().as_str();
+            let on_progress = |progress: ProveProgress| self.report_progress(&job_id, progress);
+
+            let start = Instant::now();
+            // Dropping the prove future on cancel kills the child process
+            let result = tokio::select! {
+                result = prove_in_subprocess(&input, on_progress) => Some(result),
+                _ = cancel.notified() => None,
+            };
+            let took = start.elapsed();
+
+            if !self.finish(&job_id, took) {
+                log::info!("Job {} cancelled after {}ms", job_id, took.as_millis());
+                self.metrics.observe_proof(receipt_kind, "cancelled", took);
+                continue;
+            }
+
+            let (state, receipt) = match result {
+                Some(Ok(prove_result)) => {
+                    log::info!("Job {} done in {}ms", job_id, took.as_millis());
+                    self.metrics
+                        .observe_cycles(receipt_kind, &prove_result.cycles);
+                    let state = JobState::Done {
+                        result: Box::new(prove_response(&prove_result, took.as_millis())),
+                    };
+                    (state, Some(prove_result.receipt))
+                }
+                Some(Err(e)) => {
+                    log::error!("Job {} failed: {}", job_id, e);
+                    let state = JobState::Failed {
+                        error: format!("proof generation failed: {}", e),
+                    };
+                    (state, None)
+                }
+                None => continue,
+            };
+            self.metrics.observe_proof(receipt_kind, state.name(), took);
+            self.store_result(job_id, state, receipt).await;
+        }
+    }
+
+    /// Records a finished job. The store flushes, so this runs off the async
+    /// workers.
+    async fn store_result(
+        self: &Arc<Self>,
+        job_id: String,
+        state: JobState,
+        receipt: Option<Vec<u8>>,
+    ) {
+        let queue = self.clone();
+        let stored = tokio::task::spawn_blocking(move || {
+            // Stored before the job shows as done, so it can be fetched then
+            if let Some(receipt) = receipt {
+                if let Err(e) = queue.jobs.put_receipt(&job_id, &receipt) {
+                    log::error!("Job {} receipt could not be stored: {}", job_id, e);
+                }
+            }
+            queue.record_state(&job_id, state);
+            queue.flush();
+        })
+        .await;
+        if let Err(e) = stored {
+            log::error!("Job result could not be stored: {}", e);
+        }
+    }
+
+    fn report_progress(&self, job_id: &str, progress: ProveProgress) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(job) = inner.running.iter_mut().find(|j| j.job_id == job_id) {
+            job.progress = Some(progress.clone());
+        }
+        // No subscribers is not an error
+        let _ = self.events.send(JobEvent::progress(job_id, progress));
+    }
+
+    /// A failed write is logged rather than lost silently. Not yet on disk
+    /// until `flush`, so it can be called under the lock.
+    fn record_state(&self, job_id: &str, state: JobState) {
+        if let Err(e) = self.jobs.set_state(job_id, state.clone()) {
+            log::error!("Job {} state could not be stored: {}", job_id, e);
+        }
+        let _ = self.events.send(JobEvent::state(job_id, state));
+    }
+
+    /// Persists the recorded states. Blocks on disk I/O, so never called
+    /// under the lock.
+    fn flush(&self) {
+        if let Err(e) = self.jobs.flush() {
+            log::error!("Job store could not be flushed: {}", e);
+        }
+    }
+}
+
+// ─── Prover process ───────────────────────────────────────────────────────────
+
+/// One line of the prover process's stdout.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ProverMessage {
+    Progress(ProveProgress),
+    /// Always the last message.
+    Result(std::result::Result<Box<ProveResult>, String>),
+}
+
+/// Proves `input` in a child copy of this binary, passing on its progress as
+/// it arrives. Killed if the future is dropped.
+async fn prove_in_subprocess(
+    input: &ProveInput,
+    on_progress: impl Fn(ProveProgress),
+) -> Result<ProveResult> {
+    let mut child = tokio::process::Command::new(std::env::current_exe()?)
+        .arg(PROVE_JOB_ARG)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("prover process has no stdin"))?;
+    stdin.write_all(&serde_json::to_vec(input)?).await?;
+    drop(stdin);
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("prover process has no stdout"))?;
+    let mut lines = BufReader::new(stdout).lines();
+    let mut result = None;
+    while let Some(line) = lines.next_line().await? {
+        // Anything that isn't a message is incidental output
+        match serde_json::from_str(&line) {
+            Ok(ProverMessage::Progress(progress)) => on_progress(progress),
+            Ok(ProverMessage::Result(r)) => result = Some(r),
+            Err(_) => {}
+        }
+    }
+
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(anyhow!("prover process exited with {}", status));
+    }
+    result
+        .ok_or_else(|| anyhow!("prover process printed no result"))?
+        .map(|result| *result)
+        .map_err(|e| anyhow!(e))
+}
+
+/// Entry point of the child process: reads a ProveInput from stdin, proves it
+/// and prints progress and then the result, one JSON line each.
+pub fn run_prove_job() -> std::io::Result<()> {
+    let mut raw = Vec::new();
+    std::io::stdin().read_to_end(&mut raw)?;
+    let input: ProveInput = serde_json::from_slice(&raw).map_err(std::io::Error::other)?;
+
+    let result = prove_game_with_progress(&input, |progress| {
+        if let Err(e) = send_message(&ProverMessage::Progress(progress)) {
+            log::warn!("Progress could not be reported: {}", e);
+        }
+    })
+    .map(Box::new)
+    .map_err(|e| e.to_string());
+
+    send_message(&ProverMessage::Result(result))
+}
+
+fn send_message(message: &ProverMessage) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, message).map_err(std::io::Error::other)?;
+    writeln!(stdout)?;
+    stdout.flush()
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ─── /prove rate limits ───────────────────────────────────────────────────────
+
+/// Sliding-window limits on proof submissions, per player and per client IP,
+/// held in memory. A limit of 0 disables that key.
+pub struct ProveRateLimits {
+    per_player: usize,
+    per_ip: usize,
+    window: Duration,
+    inner: Mutex<Windows>,
+}
+
+#[derive(Default)]
+struct Windows {
+    players: HashMap<[u8; 32], VecDeque<Instant>>,
+    ips: HashMap<IpAddr, VecDeque<Instant>>,
+}
+
+/// Which limit turned a submission away, and when to try again.
+pub struct Limited {
+    pub scope: &'static str,
+    pub limit: usize,
+    pub retry_after: Duration,
+}
+
+impl ProveRateLimits {
+    pub fn new(per_player: usize, per_ip: usize, window: Duration) -> Self {
+        ProveRateLimits {
+            per_player,
+            per_ip,
+            window,
+            inner: Mutex::new(Windows::default()),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Counts a submission against both limits, or against neither if either
+    /// is already used up.
+    pub fn acquire(&self, player: &[u8; 32], ip: Option<IpAddr>) -> Result<(), Limited> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+
+        // Forget submissions that have left the window
+        inner
+            .players
+            .retain(|_, hits| prune(hits, self.window, now));
+        inner.ips.retain(|_, hits| prune(hits, self.window, now));
+
+        let player_hits = inner.players.get(player);
+        check("player", self.per_player, player_hits, self.window, now)?;
+        if let Some(ip) = ip {
+            check("ip", self.per_ip, inner.ips.get(&ip), self.window, now)?;
+        }
+
+        if self.per_player > 0 {
+            inner.players.entry(*player).or_default().push_back(now);
+        }
+        if let (Some(ip), true) = (ip, self.per_ip > 0) {
+            inner.ips.entry(ip).or_default().push_back(now);
+        }
+        Ok(())
+    }
+}
+
+// ─── Per-IP rate limits ───────────────────────────────────────────────────────
+
+/// Sliding-window limit on calls per client IP, for the unsigned endpoints
+/// where the IP is the only key: /simulate, whose calls each cost a Stellar
+/// RPC round trip, and /verify, whose calls each cost a proof verification.
+/// A limit of 0 disables it.
+pub struct IpRateLimit {
+    per_ip: usize,
+    window: Duration,
+    ips: Mutex<HashMap<IpAddr, VecDeque<Instant>>>,
+}
+
+impl IpRateLimit {
+    pub fn new(per_ip: usize, window: Duration) -> Self {
+        IpRateLimit {
+            per_ip,
+            window,
+            ips: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Counts a call against the caller's IP. Callers without a known IP
+    /// are not limited.
+    pub fn acquire(&self, ip: Option<IpAddr>) -> Result<(), Limited> {
+        let (Some(ip), true) = (ip, self.per_ip > 0) else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        let mut ips = self.ips.lock().unwrap();
+        ips.retain(|_, hits| prune(hits, self.window, now));
+
+        check("ip", self.per_ip, ips.get(&ip), self.window, now)?;
+        ips.entry(ip).or_default().push_back(now);
+        Ok(())
+    }
+}
+
+// ─── Shared window helpers ────────────────────────────────────────────────────
+
+/// Drops hits older than the window. Returns whether any are left.
+fn prune(hits: &mut VecDeque<Instant>, window: Duration, now: Instant) -> bool {
+    while hits
+        .front()
+        .is_some_and(|t| now.duration_since(*t) >= window)
+    {
+        hits.pop_front();
+    }
+    !hits.is_empty()
+}
+
+fn check(
+    scope: &'static str,
+    limit: usize,
+    hits: Option<&VecDeque<Instant>>,
+    window: Duration,
+    now: Instant,
+) -> Result<(), Limited> {
+    let Some(hits) = hits else { return Ok(()) };
+    if limit == 0 || hits.len() < limit {
+        return Ok(());
+    }
+    // A slot frees up when the oldest hit in the window ages out
+    let oldest = hits[hits.len() - limit];
+    Err(Limited {
+        scope,
+        limit,
+        retry_after: window.saturating_sub(now.duration_since(oldest)),
+    })
+}
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hex::encode as hex_encode;
+use serde::{Deserialize, Serialize};
+
+use host::{
+    decode_receipt, journal_response, verify_receipt, verify_seal, JournalResponse, VerifiedProof,
+};
+use risc0_zkvm::Receipt;
+
+use crate::{image_id_hex, parse_hex_32};
+
+pub const VERIFY_ARG: &str = "verify";
+
+const USAGE: &str = "usage: host verify <receipt.cbor> [--image-id HEX]\n       host verify --seal HEX --journal HEX [--image-id HEX]";
+
+// ─── Verify request / response ────────────────────────────────────────────────
+
+/// Body of POST /verify: either `receipt_base64` (a CBOR receipt, as served
+/// by GET /receipt/{job_id}), or `seal_hex` with `journal_bytes_hex`. The
+/// image id defaults to this server's guest.
+#[derive(Debug, Deserialize)]
+pub struct VerifyRequest {
+    pub receipt_base64: Option<String>,
+    pub seal_hex: Option<String>,
+    pub journal_bytes_hex: Option<String>,
+    pub image_id_hex: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyResponse {
+    pub verified: bool,
+    pub image_id_hex: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_sha256_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_bytes_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal: Option<JournalResponse>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What a verify request asks to check, decoded but not yet verified.
+pub enum Proof {
+    Receipt(Box<Receipt>),
+    Seal {
+        seal: Vec<u8>,
+        journal_bytes: Vec<u8>,
+    },
+}
+
+impl VerifyRequest {
+    /// Decodes the request, or says why it is malformed.
+    pub fn decode(&self) -> Result<(Proof, [u8; 32])> {
+        let image_id = image_id_or_default(self.image_id_hex.as_deref())?;
+        let proof = match (
+            &self.receipt_base64,
+            &self.seal_hex,
+            &self.journal_bytes_hex,
+        ) {
+            (Some(receipt), None, None) => {
+                Proof::from_cbor(&STANDARD.decode(receipt).context("receipt_base64")?)?
+            }
+            (None, Some(seal), Some(journal)) => Proof::from_hex(seal, journal)?,
+            _ => {
+                return Err(anyhow!(
+                    "send either receipt_base64, or seal_hex with journal_bytes_hex"
+                ))
+            }
+        };
+        Ok((proof, image_id))
+    }
+}
+
+impl Proof {
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        Ok(Proof::Receipt(Box::new(decode_receipt(bytes)?)))
+    }
+
+    fn from_hex(seal: &str, journal_bytes: &str) -> Result<Self> {
+        Ok(Proof::Seal {
+            seal: hex::decode(seal.trim_start_matches("0x")).context("seal")?,
+            journal_bytes: hex::decode(journal_bytes.trim_start_matches("0x"))
+                .context("journal bytes")?,
+        })
+    }
+}
+
+/// The image id to verify against: the given one, or this server's guest.
+pub fn image_id_or_default(hex: Option<&str>) -> Result<[u8; 32]> {
+    match hex {
+        Some(hex) => parse_hex_32(hex).context("image id"),
+        None => parse_hex_32(&image_id_hex()),
+    }
+}
+
+/// Verifies a decoded proof. Blocking: Groth16 and STARK verification take
+/// milliseconds of CPU.
+pub fn verify_proof(proof: &Proof, image_id: [u8; 32]) -> VerifyResponse {
+    let result = match proof {
+        Proof::Receipt(receipt) => verify_receipt(receipt, image_id),
+        Proof::Seal {
+            seal,
+            journal_bytes,
+        } => verify_seal(seal, journal_bytes, image_id),
+    };
+    verify_response(result, image_id)
+}
+
+fn verify_response(result: Result<VerifiedProof>, image_id: [u8; 32]) -> VerifyResponse {
+    match result {
+        Ok(verified) => VerifyResponse {
+            verified: true,
+            image_id_hex: hex_encode(verified.image_id),
+            receipt_kind: Some(verified.receipt_kind.as_str().to_string()),
+            journal_sha256_hex: Some(hex_encode(verified.journal_sha256)),
+            journal_bytes_hex: Some(format!("0x{}", hex_encode(&verified.journal_bytes))),
+            journal: Some(journal_response(&verified.journal)),
+            error: None,
+        },
+        Err(e) => VerifyResponse {
+            verified: false,
+            image_id_hex: hex_encode(image_id),
+            receipt_kind: None,
+            journal_sha256_hex: None,
+            journal_bytes_hex: None,
+            journal: None,
+            error: Some(format!("{:#}", e)),
+        },
+    }
+}
+
+// ─── CLI ──────────────────────────────────────────────────────────────────────
+
+/// `host verify ...`: verifies a receipt file, or a seal and journal, and
+/// prints the same JSON as POST /verify. Exits 1 if it doesn't verify.
+pub fn run_verify_cli(args: impl Iterator<Item = String>) -> std::io::Result<()> {
+    let (proof, image_id) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{:#}\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let response = verify_proof(&proof, image_id);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&response).map_err(std::io::Error::other)?
+    );
+    if !response.verified {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(Proof, [u8; 32])> {
+    let (mut receipt_path, mut seal, mut journal, mut image_id) = (None, None, None, None);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| anyhow!("{} needs a value", name));
+        match arg.as_str() {
+            "--seal" => seal = Some(value("--seal")?),
+            "--journal" => journal = Some(value("--journal")?),
+            "--image-id" => image_id = Some(value("--image-id")?),
+            path if !path.starts_with("--") && receipt_path.is_none() => {
+                receipt_path = Some(path.to_string())
+            }
+            other => return Err(anyhow!("unexpected argument {}", other)),
+        }
+    }
+
+    let proof = match (receipt_path, seal, journal) {
+        (Some(path), None, None) => {
+            let bytes =
+                std::fs::read(&path).with_context(|| format!("reading receipt {}", path))?;
+            Proof::from_cbor(&bytes).with_context(|| format!("decoding receipt {}", path))?
+        }
+        (None, Some(seal), Some(journal)) => Proof::from_hex(&seal, &journal)?,
+        _ => return Err(anyhow!("pass a receipt file, or --seal with --journal")),
+    };
+    Ok((proof, image_id_or_default(image_id.as_deref())?))
+}